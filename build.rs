@@ -47,6 +47,24 @@ impl_name!(
     "MsgSubmitCommunityVerification"
 );
 
+impl_name!(
+    fiamma::zkpverify::MsgUpdateDaSubmissionResults,
+    "fiamma.zkpverify",
+    "MsgUpdateDASubmissionResults"
+);
+
+impl_name!(
+    fiamma::zkpverify::MsgUpdateDaSubmitter,
+    "fiamma.zkpverify",
+    "MsgUpdateDASubmitter"
+);
+
+impl_name!(
+    fiamma::zkpverify::MsgUpdateParams,
+    "fiamma.zkpverify",
+    "MsgUpdateParams"
+);
+
 impl_name!(
     fiamma::bitvmstaker::MsgCreateStaker,
     "fiamma.bitvmstaker",
@@ -59,6 +77,18 @@ impl_name!(
     "MsgRemoveStaker"
 );
 
+impl_name!(
+    fiamma::bitvmstaker::MsgUpdateCommitteeAddress,
+    "fiamma.bitvmstaker",
+    "MsgUpdateCommitteeAddress"
+);
+
+impl_name!(
+    fiamma::bitvmstaker::MsgUpdateParams,
+    "fiamma.bitvmstaker",
+    "MsgUpdateParams"
+);
+
 impl_name!(
     fiamma::bitvmstaker::MsgRegisterVk,
     "fiamma.bitvmstaker",
@@ -72,8 +102,56 @@ impl_name!(
 );
 "#;
 
+    // Query results and params that get JSON representations under the `serde` feature.
+    let serde_types = [
+        "fiamma.zkpverify.Params",
+        "fiamma.zkpverify.MsgUpdateParams",
+        "fiamma.zkpverify.DASubmissionResult",
+        "fiamma.zkpverify.DASubmissionData",
+        "fiamma.zkpverify.ProofData",
+        "fiamma.zkpverify.VerifyResult",
+        "fiamma.zkpverify.BitVMChallengeData",
+        "fiamma.bitvmstaker.Params",
+        "fiamma.bitvmstaker.MsgUpdateParams",
+        "fiamma.bitvmstaker.StakerInfo",
+    ];
+    let serde_fields = [
+        ("fiamma.zkpverify.ProofData.proof", "hex_bytes"),
+        ("fiamma.zkpverify.ProofData.public_input", "hex_bytes"),
+        ("fiamma.zkpverify.ProofData.vk", "hex_bytes"),
+        ("fiamma.zkpverify.ProofData.proof_system", "proof_system"),
+        ("fiamma.zkpverify.ProofData.data_location", "data_location"),
+        ("fiamma.zkpverify.BitVMChallengeData.witness", "hex_bytes"),
+        ("fiamma.zkpverify.BitVMChallengeData.vk", "hex_bytes"),
+        ("fiamma.zkpverify.VerifyResult.proof_system", "proof_system"),
+        (
+            "fiamma.zkpverify.VerifyResult.status",
+            "verification_status",
+        ),
+        (
+            "fiamma.zkpverify.DASubmissionResult.data_location",
+            "data_location",
+        ),
+    ];
+
     let out_dir = "src/generated";
-    tonic_build::configure().out_dir(out_dir).compile(
+    let mut builder = tonic_build::configure().out_dir(out_dir);
+    for path in serde_types {
+        builder = builder.type_attribute(
+            path,
+            r#"#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]"#,
+        );
+    }
+    for (path, with) in serde_fields {
+        builder = builder.field_attribute(
+            path,
+            format!(
+                r#"#[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::{}"))]"#,
+                with
+            ),
+        );
+    }
+    builder.compile(
         &[
             "src/protos/fiamma/zkpverify/tx.proto",
             "src/protos/fiamma/zkpverify/query.proto",
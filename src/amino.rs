@@ -0,0 +1,406 @@
+use crate::types::{
+    BitvmstakerMsgUpdateParams, MsgCreateStaker, MsgRegisterVK, MsgRemoveStaker, MsgRemoveVK,
+    MsgSubmitCommunityVerification, MsgSubmitProof, MsgUpdateCommitteeAddress,
+    MsgUpdateDASubmissionResults, MsgUpdateDASubmitter, ZkpverifyMsgUpdateParams,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use cosmrs::{
+    bank::MsgSend,
+    proto::traits::Name,
+    tx::{AccountNumber, Body, Fee, Msg, SequenceNumber},
+    Any, Coin, ErrorReport, Result,
+};
+use prost::Message;
+use serde_json::{json, Map, Value};
+
+/// `amino.name` of `fiamma.zkpverify.MsgUpdateParams`.
+pub const ZKPVERIFY_UPDATE_PARAMS_AMINO_NAME: &str =
+    "github.com/fiamma-chain/fiamma/x/zkpverify/MsgUpdateParams";
+/// `amino.name` of `fiamma.bitvmstaker.MsgUpdateParams`.
+pub const BITVMSTAKER_UPDATE_PARAMS_AMINO_NAME: &str =
+    "github.com/fiamma-chain/fiamma/x/bitvmstaker/MsgUpdateParams";
+
+/// Legacy amino JSON encoding of a message, used by `SIGN_MODE_LEGACY_AMINO_JSON`.
+///
+/// Fields follow the chain's amino JSON rules: proto field names, zero values omitted,
+/// bytes as standard base64 and 64-bit integers as strings.
+pub trait AminoMsg: Msg {
+    /// The message's `amino.name` option. Fiamma's messages declare none, and the SDK 0.50
+    /// aminojson encoder then uses the type URL.
+    fn amino_type() -> String {
+        Self::Proto::type_url()
+    }
+
+    fn amino_value(&self) -> Map<String, Value>;
+
+    fn to_amino_json(&self) -> Value {
+        json!({
+            "type": Self::amino_type(),
+            "value": self.amino_value(),
+        })
+    }
+}
+
+impl AminoMsg for MsgSend {
+    fn amino_type() -> String {
+        "cosmos-sdk/MsgSend".to_string()
+    }
+
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        insert_str(&mut value, "from_address", self.from_address.as_ref());
+        insert_str(&mut value, "to_address", self.to_address.as_ref());
+        // `amount` is marked `dont_omitempty`.
+        value.insert("amount".to_string(), coins_json(&self.amount));
+        value
+    }
+}
+
+/// Encodes a message for `SIGN_MODE_LEGACY_AMINO_JSON`. Only Fiamma's messages, including both
+/// modules' `MsgUpdateParams`, and `MsgSend` are supported; authz and feegrant messages must be
+/// signed with `SIGN_MODE_DIRECT`.
+pub fn any_to_amino_json(any: &Any) -> Result<Value> {
+    amino_json::<MsgSubmitProof>(any)
+        .or_else(|| amino_json::<MsgSubmitCommunityVerification>(any))
+        .or_else(|| amino_json::<MsgUpdateDASubmissionResults>(any))
+        .or_else(|| amino_json::<MsgUpdateDASubmitter>(any))
+        .or_else(|| amino_json::<MsgCreateStaker>(any))
+        .or_else(|| amino_json::<MsgRemoveStaker>(any))
+        .or_else(|| amino_json::<MsgUpdateCommitteeAddress>(any))
+        .or_else(|| amino_json::<MsgRegisterVK>(any))
+        .or_else(|| amino_json::<MsgRemoveVK>(any))
+        .or_else(|| amino_json::<MsgSend>(any))
+        .or_else(|| {
+            update_params_json::<ZkpverifyMsgUpdateParams>(
+                any,
+                ZKPVERIFY_UPDATE_PARAMS_AMINO_NAME,
+                |msg| &msg.authority,
+            )
+        })
+        .or_else(|| {
+            update_params_json::<BitvmstakerMsgUpdateParams>(
+                any,
+                BITVMSTAKER_UPDATE_PARAMS_AMINO_NAME,
+                |msg| &msg.authority,
+            )
+        })
+        .unwrap_or_else(|| {
+            Err(ErrorReport::msg(format!(
+                "{} cannot be signed with SIGN_MODE_LEGACY_AMINO_JSON, use SIGN_MODE_DIRECT",
+                any.type_url
+            )))
+        })
+}
+
+/// Fails on the first message [`any_to_amino_json`] cannot encode.
+pub fn check_amino_json(msgs: &[Any]) -> Result<()> {
+    msgs.iter()
+        .try_for_each(|any| any_to_amino_json(any).map(drop))
+}
+
+fn amino_json<M: AminoMsg>(any: &Any) -> Option<Result<Value>> {
+    (any.type_url == M::Proto::type_url()).then(|| M::from_any(any).map(|msg| msg.to_amino_json()))
+}
+
+/// Both modules' `Params` are empty and marked `dont_omitempty`, so `params` is always `{}`.
+fn update_params_json<M: Message + Name + Default>(
+    any: &Any,
+    amino_type: &str,
+    authority: fn(&M) -> &str,
+) -> Option<Result<Value>> {
+    (any.type_url == M::type_url()).then(|| {
+        let msg: M = any.to_msg()?;
+        let mut value = Map::new();
+        insert_str(&mut value, "authority", authority(&msg));
+        value.insert("params".to_string(), json!({}));
+        Ok(json!({ "type": amino_type, "value": value }))
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StdSignDoc {
+    pub account_number: AccountNumber,
+    pub chain_id: String,
+    pub fee: Fee,
+    pub memo: String,
+    pub msgs: Vec<Value>,
+    pub sequence: SequenceNumber,
+    pub timeout_height: u64,
+}
+
+impl StdSignDoc {
+    pub fn new(
+        body: &Body,
+        fee: &Fee,
+        chain_id: &str,
+        account_number: AccountNumber,
+        sequence: SequenceNumber,
+    ) -> Result<Self> {
+        Ok(Self {
+            account_number,
+            chain_id: chain_id.to_string(),
+            fee: fee.clone(),
+            memo: body.memo.clone(),
+            msgs: body
+                .messages
+                .iter()
+                .map(any_to_amino_json)
+                .collect::<Result<_>>()?,
+            sequence,
+            timeout_height: body.timeout_height.value(),
+        })
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut fee = Map::new();
+        fee.insert("amount".to_string(), coins_json(&self.fee.amount));
+        fee.insert("gas".to_string(), self.fee.gas_limit.to_string().into());
+        if let Some(granter) = &self.fee.granter {
+            fee.insert("granter".to_string(), granter.to_string().into());
+        }
+        if let Some(payer) = &self.fee.payer {
+            fee.insert("payer".to_string(), payer.to_string().into());
+        }
+
+        let mut doc = Map::new();
+        doc.insert(
+            "account_number".to_string(),
+            self.account_number.to_string().into(),
+        );
+        doc.insert("chain_id".to_string(), self.chain_id.clone().into());
+        doc.insert("fee".to_string(), fee.into());
+        doc.insert("memo".to_string(), self.memo.clone().into());
+        doc.insert("msgs".to_string(), self.msgs.clone().into());
+        doc.insert("sequence".to_string(), self.sequence.to_string().into());
+        if self.timeout_height != 0 {
+            doc.insert(
+                "timeout_height".to_string(),
+                self.timeout_height.to_string().into(),
+            );
+        }
+        doc.into()
+    }
+
+    /// Canonical sign bytes: keys sorted, no whitespace, and `<`, `>`, `&` escaped the way
+    /// Go's `encoding/json` does, so the bytes match what the chain verifies.
+    pub fn sign_bytes(&self) -> Result<Vec<u8>> {
+        let json = serde_json::to_string(&sorted(self.to_json()))?
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026");
+        Ok(json.into_bytes())
+    }
+}
+
+fn coins_json(coins: &[Coin]) -> Value {
+    coins
+        .iter()
+        .map(|coin| json!({ "amount": coin.amount.to_string(), "denom": coin.denom.as_ref() }))
+        .collect()
+}
+
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sorted(v))).collect())
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted).collect()),
+        value => value,
+    }
+}
+
+pub(crate) fn insert_str(value: &mut Map<String, Value>, key: &str, s: &str) {
+    if !s.is_empty() {
+        value.insert(key.to_string(), s.into());
+    }
+}
+
+pub(crate) fn insert_bytes(value: &mut Map<String, Value>, key: &str, bytes: &[u8]) {
+    if !bytes.is_empty() {
+        value.insert(key.to_string(), BASE64.encode(bytes).into());
+    }
+}
+
+pub(crate) fn insert_u64(value: &mut Map<String, Value>, key: &str, n: u64) {
+    if n != 0 {
+        value.insert(key.to_string(), n.to_string().into());
+    }
+}
+
+pub(crate) fn insert_i32(value: &mut Map<String, Value>, key: &str, n: i32) {
+    if n != 0 {
+        value.insert(key.to_string(), n.into());
+    }
+}
+
+pub(crate) fn insert_bool(value: &mut Map<String, Value>, key: &str, b: bool) {
+    if b {
+        value.insert(key.to_string(), b.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{any_to_amino_json, check_amino_json, StdSignDoc};
+    use crate::{
+        authz::MsgExec,
+        chain::*,
+        types::{
+            BitvmstakerMsgUpdateParams, BitvmstakerParams, MsgRegisterVK,
+            MsgSubmitCommunityVerification, MsgSubmitProof, ZkpverifyMsgUpdateParams,
+        },
+    };
+    use cosmrs::{
+        bank::MsgSend,
+        tx::{BodyBuilder, Fee, Msg},
+        AccountId, Any, Coin,
+    };
+
+    const CREATOR: &str = "fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp";
+
+    fn fee() -> Fee {
+        Fee::from_amount_and_gas(
+            Coin {
+                amount: 2000,
+                denom: DENOM.parse().unwrap(),
+            },
+            200_000_u64,
+        )
+    }
+
+    fn creator() -> AccountId {
+        CREATOR.parse().unwrap()
+    }
+
+    #[test]
+    fn community_verification_sign_bytes() {
+        let msg = MsgSubmitCommunityVerification {
+            creator: creator(),
+            proof_id: "8a17276c37500fe1f0b277f21205592eac037b60f8a7021713ed2b99fe4f78f2"
+                .to_string(),
+            verify_result: true,
+        };
+        let body = BodyBuilder::new()
+            .msg(msg.to_any().unwrap())
+            .memo("a<b>&c")
+            .finish();
+        let doc = StdSignDoc::new(&body, &fee(), CHAIN_ID, 12, 5).unwrap();
+        let expected = concat!(
+            r#"{"account_number":"12","chain_id":"fiamma-testnet-1","#,
+            r#""fee":{"amount":[{"amount":"2000","denom":"ufia"}],"gas":"200000"},"#,
+            r#""memo":"a\u003cb\u003e\u0026c","#,
+            r#""msgs":[{"type":"/fiamma.zkpverify.MsgSubmitCommunityVerification","#,
+            r#""value":{"creator":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp","#,
+            r#""proof_id":"8a17276c37500fe1f0b277f21205592eac037b60f8a7021713ed2b99fe4f78f2","#,
+            r#""verify_result":true}}],"sequence":"5"}"#
+        );
+        assert_eq!(
+            String::from_utf8(doc.sign_bytes().unwrap()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn register_vk_sign_bytes() {
+        let msg = MsgRegisterVK {
+            creator: creator(),
+            vk: vec![0xde, 0xad, 0xbe, 0xef, 0x01],
+        };
+        let mut fee = fee();
+        fee.granter = Some(creator());
+        let body = BodyBuilder::new()
+            .msg(msg.to_any().unwrap())
+            .timeout_height(100_u32)
+            .finish();
+        let doc = StdSignDoc::new(&body, &fee, CHAIN_ID, 0, 0).unwrap();
+        let expected = concat!(
+            r#"{"account_number":"0","chain_id":"fiamma-testnet-1","#,
+            r#""fee":{"amount":[{"amount":"2000","denom":"ufia"}],"gas":"200000","#,
+            r#""granter":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp"},"#,
+            r#""memo":"","#,
+            r#""msgs":[{"type":"/fiamma.bitvmstaker.MsgRegisterVK","#,
+            r#""value":{"creator":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp","vk":"3q2+7wE="}}],"#,
+            r#""sequence":"0","timeout_height":"100"}"#
+        );
+        assert_eq!(
+            String::from_utf8(doc.sign_bytes().unwrap()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn submit_proof_omits_empty_fields() {
+        let msg = MsgSubmitProof {
+            creator: creator(),
+            proof_system: "GROTH16_BN254_BITVM".to_string(),
+            proof: vec![1, 2, 3],
+            public_input: vec![],
+            vk: vec![4],
+            namespace: "test-namespace".to_string(),
+            data_location: String::new(),
+        };
+        let json = any_to_amino_json(&msg.to_any().unwrap()).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(concat!(
+            r#"{"type":"/fiamma.zkpverify.MsgSubmitProof","#,
+            r#""value":{"creator":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp","#,
+            r#""namespace":"test-namespace","proof":"AQID","#,
+            r#""proof_system":"GROTH16_BN254_BITVM","vk":"BA=="}}"#
+        ))
+        .unwrap();
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn send_uses_sdk_amino_name() {
+        let msg = MsgSend {
+            from_address: creator(),
+            to_address: creator(),
+            amount: vec![],
+        };
+        let json = any_to_amino_json(&msg.to_any().unwrap()).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(concat!(
+            r#"{"type":"cosmos-sdk/MsgSend","#,
+            r#""value":{"amount":[],"from_address":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp","#,
+            r#""to_address":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp"}}"#
+        ))
+        .unwrap();
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn update_params_uses_amino_name() {
+        let msg = ZkpverifyMsgUpdateParams {
+            authority: CREATOR.to_string(),
+            params: None,
+        };
+        let json = any_to_amino_json(&Any::from_msg(&msg).unwrap()).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(concat!(
+            r#"{"type":"github.com/fiamma-chain/fiamma/x/zkpverify/MsgUpdateParams","#,
+            r#""value":{"authority":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp","params":{}}}"#
+        ))
+        .unwrap();
+        assert_eq!(json, expected);
+
+        let msg = BitvmstakerMsgUpdateParams {
+            authority: CREATOR.to_string(),
+            params: Some(BitvmstakerParams {}),
+        };
+        let json = any_to_amino_json(&Any::from_msg(&msg).unwrap()).unwrap();
+        assert_eq!(json["type"], super::BITVMSTAKER_UPDATE_PARAMS_AMINO_NAME);
+        assert_eq!(json["value"]["params"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn rejects_messages_without_amino_encoding() {
+        let exec = MsgExec {
+            grantee: creator(),
+            msgs: vec![],
+        }
+        .to_any()
+        .unwrap();
+        let err = check_amino_json(&[exec]).unwrap_err();
+        assert!(err.to_string().contains("/cosmos.authz.v1beta1.MsgExec"));
+    }
+}
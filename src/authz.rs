@@ -0,0 +1,261 @@
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{
+    GenericAuthorization, Grant, MsgExec as ProtoMsgExec, MsgGrant as ProtoMsgGrant,
+    MsgRevoke as ProtoMsgRevoke,
+};
+use cosmrs::{
+    proto::traits::{Message, Name},
+    tx::Msg,
+    AccountId, Any, ErrorReport, Result,
+};
+use std::time::SystemTime;
+
+pub const GENERIC_AUTHORIZATION_TYPE_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+pub const MSG_REVOKE_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgRevoke";
+
+pub fn type_url<M: Msg>() -> String {
+    M::Proto::type_url()
+}
+
+/// Grants `grantee` a `GenericAuthorization` to execute `msg_type_url` on behalf of `granter`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MsgGrant {
+    pub granter: AccountId,
+    pub grantee: AccountId,
+    pub msg_type_url: String,
+    pub expiration: Option<SystemTime>,
+}
+
+impl Msg for MsgGrant {
+    type Proto = ProtoMsgGrant;
+}
+
+impl TryFrom<ProtoMsgGrant> for MsgGrant {
+    type Error = ErrorReport;
+
+    fn try_from(proto: ProtoMsgGrant) -> Result<MsgGrant> {
+        MsgGrant::try_from(&proto)
+    }
+}
+
+impl TryFrom<&ProtoMsgGrant> for MsgGrant {
+    type Error = ErrorReport;
+
+    fn try_from(proto: &ProtoMsgGrant) -> Result<MsgGrant> {
+        let grant = proto
+            .grant
+            .as_ref()
+            .ok_or(ErrorReport::msg("Empty grant in MsgGrant"))?;
+        let authorization = grant
+            .authorization
+            .as_ref()
+            .ok_or(ErrorReport::msg("Empty authorization in MsgGrant"))?;
+        if authorization.type_url != GENERIC_AUTHORIZATION_TYPE_URL {
+            return Err(ErrorReport::msg(format!(
+                "Unsupported authorization type {}",
+                authorization.type_url
+            )));
+        }
+        let authorization = GenericAuthorization::decode(authorization.value.as_slice())?;
+        Ok(MsgGrant {
+            granter: proto.granter.parse()?,
+            grantee: proto.grantee.parse()?,
+            msg_type_url: authorization.msg,
+            expiration: grant
+                .expiration
+                .clone()
+                .map(SystemTime::try_from)
+                .transpose()?,
+        })
+    }
+}
+
+impl From<MsgGrant> for ProtoMsgGrant {
+    fn from(msg: MsgGrant) -> ProtoMsgGrant {
+        ProtoMsgGrant::from(&msg)
+    }
+}
+
+impl From<&MsgGrant> for ProtoMsgGrant {
+    fn from(msg: &MsgGrant) -> ProtoMsgGrant {
+        let authorization = GenericAuthorization {
+            msg: msg.msg_type_url.clone(),
+        };
+        ProtoMsgGrant {
+            granter: msg.granter.to_string(),
+            grantee: msg.grantee.to_string(),
+            grant: Some(Grant {
+                authorization: Some(Any {
+                    type_url: GENERIC_AUTHORIZATION_TYPE_URL.to_string(),
+                    value: authorization.encode_to_vec(),
+                }),
+                expiration: msg.expiration.map(Into::into),
+            }),
+        }
+    }
+}
+
+/// Executes `msgs` as `grantee` using authorizations granted by the messages' signers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgExec {
+    pub grantee: AccountId,
+    pub msgs: Vec<Any>,
+}
+
+impl Msg for MsgExec {
+    type Proto = ProtoMsgExec;
+}
+
+impl TryFrom<ProtoMsgExec> for MsgExec {
+    type Error = ErrorReport;
+
+    fn try_from(proto: ProtoMsgExec) -> Result<MsgExec> {
+        MsgExec::try_from(&proto)
+    }
+}
+
+impl TryFrom<&ProtoMsgExec> for MsgExec {
+    type Error = ErrorReport;
+
+    fn try_from(proto: &ProtoMsgExec) -> Result<MsgExec> {
+        Ok(MsgExec {
+            grantee: proto.grantee.parse()?,
+            msgs: proto.msgs.clone(),
+        })
+    }
+}
+
+impl From<MsgExec> for ProtoMsgExec {
+    fn from(msg: MsgExec) -> ProtoMsgExec {
+        ProtoMsgExec::from(&msg)
+    }
+}
+
+impl From<&MsgExec> for ProtoMsgExec {
+    fn from(msg: &MsgExec) -> ProtoMsgExec {
+        ProtoMsgExec {
+            grantee: msg.grantee.to_string(),
+            msgs: msg.msgs.clone(),
+        }
+    }
+}
+
+/// `cosmos-sdk-proto` does not register a type name for `MsgRevoke`, so unlike the other
+/// messages it is converted to [`Any`] by hand rather than through [`Msg`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MsgRevoke {
+    pub granter: AccountId,
+    pub grantee: AccountId,
+    pub msg_type_url: String,
+}
+
+impl MsgRevoke {
+    pub fn to_any(&self) -> Result<Any> {
+        Ok(Any {
+            type_url: MSG_REVOKE_TYPE_URL.to_string(),
+            value: ProtoMsgRevoke::from(self).encode_to_vec(),
+        })
+    }
+
+    pub fn from_any(any: &Any) -> Result<MsgRevoke> {
+        if any.type_url != MSG_REVOKE_TYPE_URL {
+            return Err(ErrorReport::msg(format!(
+                "Invalid type URL for MsgRevoke: {}",
+                any.type_url
+            )));
+        }
+        MsgRevoke::try_from(ProtoMsgRevoke::decode(any.value.as_slice())?)
+    }
+}
+
+impl TryFrom<ProtoMsgRevoke> for MsgRevoke {
+    type Error = ErrorReport;
+
+    fn try_from(proto: ProtoMsgRevoke) -> Result<MsgRevoke> {
+        Ok(MsgRevoke {
+            granter: proto.granter.parse()?,
+            grantee: proto.grantee.parse()?,
+            msg_type_url: proto.msg_type_url,
+        })
+    }
+}
+
+impl From<&MsgRevoke> for ProtoMsgRevoke {
+    fn from(msg: &MsgRevoke) -> ProtoMsgRevoke {
+        ProtoMsgRevoke {
+            granter: msg.granter.to_string(),
+            grantee: msg.grantee.to_string(),
+            msg_type_url: msg.msg_type_url.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{type_url, MsgExec, MsgGrant, MsgRevoke};
+    use crate::{
+        types::{MsgSubmitCommunityVerification, MsgSubmitProof},
+        wallet::Wallet,
+    };
+    use cosmrs::tx::Msg;
+    use std::time::{Duration, SystemTime};
+
+    const COLD_PRIVATE_KEY: &str =
+        "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const HOT_PRIVATE_KEY: &str =
+        "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897";
+
+    #[test]
+    fn fiamma_type_urls() {
+        assert_eq!(
+            type_url::<MsgSubmitProof>(),
+            "/fiamma.zkpverify.MsgSubmitProof"
+        );
+        assert_eq!(
+            type_url::<MsgSubmitCommunityVerification>(),
+            "/fiamma.zkpverify.MsgSubmitCommunityVerification"
+        );
+    }
+
+    #[test]
+    fn grant_round_trip() {
+        let msg = MsgGrant {
+            granter: Wallet::new(COLD_PRIVATE_KEY).account_id,
+            grantee: Wallet::new(HOT_PRIVATE_KEY).account_id,
+            msg_type_url: type_url::<MsgSubmitProof>(),
+            expiration: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_900_000_000)),
+        };
+        let any = msg.to_any().unwrap();
+        assert_eq!(any.type_url, "/cosmos.authz.v1beta1.MsgGrant");
+        assert_eq!(MsgGrant::from_any(&any).unwrap(), msg);
+    }
+
+    #[test]
+    fn exec_and_revoke_round_trip() {
+        let cold = Wallet::new(COLD_PRIVATE_KEY);
+        let hot = Wallet::new(HOT_PRIVATE_KEY);
+        let verification = MsgSubmitCommunityVerification {
+            creator: cold.account_id.clone(),
+            proof_id: "8a17276c37500fe1f0b277f21205592eac037b60f8a7021713ed2b99fe4f78f2"
+                .to_string(),
+            verify_result: true,
+        };
+        let exec = MsgExec {
+            grantee: hot.account_id.clone(),
+            msgs: vec![verification.to_any().unwrap()],
+        };
+        let decoded = MsgExec::from_any(&exec.to_any().unwrap()).unwrap();
+        assert_eq!(
+            MsgSubmitCommunityVerification::from_any(&decoded.msgs[0]).unwrap(),
+            verification
+        );
+
+        let revoke = MsgRevoke {
+            granter: cold.account_id,
+            grantee: hot.account_id,
+            msg_type_url: type_url::<MsgSubmitCommunityVerification>(),
+        };
+        let any = revoke.to_any().unwrap();
+        assert_eq!(any.type_url, "/cosmos.authz.v1beta1.MsgRevoke");
+        assert_eq!(MsgRevoke::from_any(&any).unwrap(), revoke);
+    }
+}
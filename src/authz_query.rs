@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+
+use crate::{
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
+};
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{
+    query_client::QueryClient as ProtoQueryClient, Grant, GrantAuthorization,
+    QueryGranteeGrantsRequest, QueryGranterGrantsRequest, QueryGrantsRequest,
+};
+
+use cosmrs::Result;
+
+#[derive(Debug, Clone)]
+pub struct QueryClient {
+    nodes: NodePool,
+    retry_policy: RetryPolicy,
+}
+
+impl QueryClient {
+    pub fn new(rpc: &str) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]))
+    }
+
+    /// A client that routes queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool) -> Self {
+        Self {
+            nodes,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.nodes.channel(&self.retry_policy)
+    }
+
+    /// Grants from `granter` to `grantee`, restricted to `msg_type_url` unless it is empty.
+    pub async fn get_grants(
+        &self,
+        granter: &str,
+        grantee: &str,
+        msg_type_url: &str,
+    ) -> Result<Vec<Grant>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let resp = client
+            .grants(QueryGrantsRequest {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+                msg_type_url: msg_type_url.to_string(),
+                pagination: None,
+            })
+            .await?;
+        Ok(resp.get_ref().clone().grants)
+    }
+
+    pub async fn get_granter_grants(&self, granter: &str) -> Result<Vec<GrantAuthorization>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let resp = client
+            .granter_grants(QueryGranterGrantsRequest {
+                granter: granter.to_string(),
+                pagination: None,
+            })
+            .await?;
+        Ok(resp.get_ref().clone().grants)
+    }
+
+    pub async fn get_grantee_grants(&self, grantee: &str) -> Result<Vec<GrantAuthorization>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let resp = client
+            .grantee_grants(QueryGranteeGrantsRequest {
+                grantee: grantee.to_string(),
+                pagination: None,
+            })
+            .await?;
+        Ok(resp.get_ref().clone().grants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryClient;
+    use crate::{authz::type_url, types::MsgSubmitProof, wallet::Wallet};
+    const NODE: &str = "http://54.65.75.57:9090";
+    const GRANTER_PRIVATE_KEY: &str =
+        "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const GRANTEE_PRIVATE_KEY: &str =
+        "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897";
+
+    fn granter() -> String {
+        Wallet::new(GRANTER_PRIVATE_KEY).account_id.to_string()
+    }
+
+    fn grantee() -> String {
+        Wallet::new(GRANTEE_PRIVATE_KEY).account_id.to_string()
+    }
+
+    #[tokio::test]
+    async fn test_get_grants() {
+        let client = QueryClient::new(NODE);
+        let grants = client
+            .get_grants(&granter(), &grantee(), &type_url::<MsgSubmitProof>())
+            .await;
+        println!("grants: {:?}", grants);
+    }
+
+    #[tokio::test]
+    async fn test_get_granter_grants() {
+        let client = QueryClient::new(NODE);
+        let grants = client.get_granter_grants(&granter()).await;
+        println!("granter_grants: {:?}", grants);
+    }
+
+    #[tokio::test]
+    async fn test_get_grantee_grants() {
+        let client = QueryClient::new(NODE);
+        let grants = client.get_grantee_grants(&grantee()).await;
+        println!("grantee_grants: {:?}", grants);
+    }
+}
@@ -0,0 +1,171 @@
+#![allow(dead_code)]
+
+use crate::{
+    chain::DENOM,
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
+};
+use cosmos_sdk_proto::cosmos::{
+    bank::v1beta1::{
+        query_client::QueryClient as ProtoQueryClient, QueryAllBalancesRequest, QueryBalanceRequest,
+    },
+    base::{query::v1beta1::PageRequest, v1beta1::Coin as ProtoCoin},
+};
+use cosmrs::{proto::traits::Message, AccountId, Coin, ErrorReport, Result};
+use tonic::{client::Grpc, codec::ProstCodec, codegen::http::uri::PathAndQuery, Request};
+
+const SPENDABLE_BALANCE_BY_DENOM_PATH: &str = "/cosmos.bank.v1beta1.Query/SpendableBalanceByDenom";
+
+/// `QuerySpendableBalanceByDenomRequest`, added in cosmos-sdk 0.47, which the
+/// cosmos-sdk-proto release in use does not have yet.
+#[derive(Clone, PartialEq, Message)]
+struct QuerySpendableBalanceByDenomRequest {
+    #[prost(string, tag = "1")]
+    address: String,
+    #[prost(string, tag = "2")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QuerySpendableBalanceByDenomResponse {
+    #[prost(message, optional, tag = "1")]
+    balance: Option<ProtoCoin>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryClient {
+    nodes: NodePool,
+    retry_policy: RetryPolicy,
+}
+
+impl QueryClient {
+    pub fn new(rpc: &str) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]))
+    }
+
+    /// A client that routes queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool) -> Self {
+        Self {
+            nodes,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.nodes.channel(&self.retry_policy)
+    }
+
+    /// The `ufia` balance of `address`, in micro units.
+    pub async fn get_balance(&self, address: &AccountId) -> Result<u128> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let resp = client
+            .balance(QueryBalanceRequest {
+                address: address.to_string(),
+                denom: DENOM.to_string(),
+            })
+            .await?;
+        let balance = resp
+            .get_ref()
+            .clone()
+            .balance
+            .ok_or(ErrorReport::msg("Empty balance in response"))?;
+        Ok(Coin::try_from(balance)?.amount)
+    }
+
+    /// The `ufia` balance of `address` that is not locked in vesting.
+    pub async fn get_spendable_balance(&self, address: &AccountId) -> Result<u128> {
+        let mut client = Grpc::new(self.channel());
+        client
+            .ready()
+            .await
+            .map_err(|e| ErrorReport::msg(format!("Service was not ready: {}", e)))?;
+        let resp = client
+            .unary::<_, QuerySpendableBalanceByDenomResponse, _>(
+                Request::new(QuerySpendableBalanceByDenomRequest {
+                    address: address.to_string(),
+                    denom: DENOM.to_string(),
+                }),
+                PathAndQuery::from_static(SPENDABLE_BALANCE_BY_DENOM_PATH),
+                ProstCodec::default(),
+            )
+            .await?;
+        let balance = resp.into_inner().balance;
+        balance.map_or(Ok(0), |balance| Ok(Coin::try_from(balance)?.amount))
+    }
+
+    /// Every balance of `address`. Fetches every page.
+    pub async fn get_all_balances(&self, address: &AccountId) -> Result<Vec<Coin>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let mut balances = vec![];
+        let mut next_key = vec![];
+        loop {
+            let resp = client
+                .all_balances(QueryAllBalancesRequest {
+                    address: address.to_string(),
+                    pagination: Some(PageRequest {
+                        key: next_key,
+                        ..Default::default()
+                    }),
+                })
+                .await?
+                .into_inner();
+            for balance in resp.balances {
+                balances.push(Coin::try_from(balance)?);
+            }
+            next_key = resp
+                .pagination
+                .map(|page| page.next_key)
+                .unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(balances);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryClient;
+    use crate::{retry::RetryPolicy, wallet::Wallet};
+    const NODE: &str = "http://54.65.75.57:9090";
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+
+    #[tokio::test]
+    async fn test_get_balance() {
+        let client = QueryClient::new(NODE);
+        let balance = client
+            .get_balance(&Wallet::new(PRIVATE_KEY).account_id)
+            .await;
+        println!("balance: {:?}", balance);
+    }
+
+    #[tokio::test]
+    async fn test_get_all_balances() {
+        let client = QueryClient::new(NODE);
+        let balances = client
+            .get_all_balances(&Wallet::new(PRIVATE_KEY).account_id)
+            .await;
+        println!("all_balances: {:?}", balances);
+    }
+
+    #[tokio::test]
+    async fn spendable_balance_fails_without_node() {
+        let client = QueryClient::new(UNREACHABLE_NODE).with_retry_policy(RetryPolicy::none());
+        assert!(client
+            .get_spendable_balance(&Wallet::new(PRIVATE_KEY).account_id)
+            .await
+            .is_err());
+    }
+}
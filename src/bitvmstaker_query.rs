@@ -1,56 +1,211 @@
 #![allow(dead_code)]
 
+use crate::generated::cosmos::base::query::v1beta1::PageRequest;
 use crate::generated::fiamma::bitvmstaker::{
     query_client::QueryClient as ProtoQueryClient, QueryAllStakerInfoRequest,
     QueryCommitteeAddressRequest, QueryRegisteredVkListRequest, StakerInfo,
 };
+use crate::{
+    height::{served_height, AtHeight, QueryHeight},
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
+    staker_set::StakerSet,
+    vk_registry::{Vk, VkRegistry},
+};
 
 use cosmrs::{ErrorReport, Result};
 
 #[derive(Debug, Clone)]
 pub struct QueryClient {
-    rpc: String,
+    nodes: NodePool,
+    retry_policy: RetryPolicy,
 }
 
 impl QueryClient {
     pub fn new(rpc: &str) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]))
+    }
+
+    /// A client that routes queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool) -> Self {
         Self {
-            rpc: rpc.to_string(),
+            nodes,
+            retry_policy: RetryPolicy::default(),
         }
     }
 
+    /// A view of this client whose queries read state at block `height`, using the
+    /// `x-cosmos-block-height` gRPC metadata. Pruned nodes may not serve old heights.
+    pub fn at_height(&self, height: u64) -> HeightQueryClient {
+        HeightQueryClient {
+            client: self.clone(),
+            height: QueryHeight::at(height),
+        }
+    }
+
+    fn latest(&self) -> HeightQueryClient {
+        HeightQueryClient {
+            client: self.clone(),
+            height: QueryHeight::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
     pub async fn get_all_staker_info(&self) -> Result<Vec<StakerInfo>> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
-        let resp = client
-            .all_staker_info(QueryAllStakerInfoRequest { pagination: None })
-            .await?;
-        let all_staker_info = resp.get_ref().clone().all_staker_info;
-        if all_staker_info.is_empty() {
+        Ok(self.latest().get_all_staker_info().await?.value)
+    }
+
+    /// The current stakers. An empty set is not an error.
+    pub async fn get_staker_set(&self) -> Result<StakerSet> {
+        Ok(self.latest().get_staker_set().await?.value)
+    }
+
+    pub async fn get_committee_address(&self) -> Result<String> {
+        Ok(self.latest().get_committee_address().await?.value)
+    }
+
+    pub async fn get_registered_vk_list(&self) -> Result<Vec<String>> {
+        Ok(self.latest().get_registered_vk_list().await?.value)
+    }
+
+    pub async fn get_vk_registry(&self) -> Result<VkRegistry> {
+        Ok(self.latest().get_vk_registry().await?.value)
+    }
+}
+
+/// A [`QueryClient`] reading state at a fixed block height, made with
+/// [`QueryClient::at_height`]. Each result carries the height the node served it at.
+#[derive(Debug, Clone)]
+pub struct HeightQueryClient {
+    client: QueryClient,
+    height: QueryHeight,
+}
+
+impl HeightQueryClient {
+    /// The height set with [`QueryClient::at_height`].
+    pub fn height(&self) -> Option<u64> {
+        self.height.height()
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.client.nodes.channel(&self.client.retry_policy)
+    }
+
+    pub async fn get_all_staker_info(&self) -> Result<AtHeight<Vec<StakerInfo>>> {
+        let all_staker_info = self.staker_infos().await?;
+        if all_staker_info.value.is_empty() {
             return Err(ErrorReport::msg("Empty all staker info in response"));
         }
         Ok(all_staker_info)
     }
 
-    pub async fn get_committee_address(&self) -> Result<String> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
-        let resp = client
-            .committee_address(QueryCommitteeAddressRequest {})
-            .await?;
-        Ok(resp.get_ref().clone().committee_address)
+    /// The stakers at this height. An empty set is not an error.
+    pub async fn get_staker_set(&self) -> Result<AtHeight<StakerSet>> {
+        Ok(self
+            .staker_infos()
+            .await?
+            .map(|staker_infos| StakerSet::from_staker_info(&staker_infos)))
     }
 
-    pub async fn get_registered_vk_list(&self) -> Result<Vec<String>> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
+    /// Fetches every page.
+    async fn staker_infos(&self) -> Result<AtHeight<Vec<StakerInfo>>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let mut all_staker_info = vec![];
+        let mut height = None;
+        let mut next_key = vec![];
+        loop {
+            let pagination = Some(PageRequest {
+                key: next_key,
+                ..Default::default()
+            });
+            let resp = client
+                .all_staker_info(
+                    self.height
+                        .for_pages(height)
+                        .request(QueryAllStakerInfoRequest { pagination }),
+                )
+                .await?;
+            height = height.or(served_height(&resp));
+            let resp = resp.into_inner();
+            all_staker_info.extend(resp.all_staker_info);
+            next_key = resp
+                .pagination
+                .map(|page| page.next_key)
+                .unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(AtHeight {
+                    value: all_staker_info,
+                    height,
+                });
+            }
+        }
+    }
+
+    pub async fn get_committee_address(&self) -> Result<AtHeight<String>> {
+        let mut client = ProtoQueryClient::new(self.channel());
         let resp = client
-            .registered_vk_list(QueryRegisteredVkListRequest { pagination: None })
+            .committee_address(self.height.request(QueryCommitteeAddressRequest {}))
             .await?;
-        Ok(resp
-            .get_ref()
-            .clone()
-            .registered_vk_list
-            .iter()
-            .map(|vk| hex::encode(vk))
-            .collect())
+        let committee_address = resp.get_ref().clone().committee_address;
+        Ok(AtHeight::from_response(&resp, committee_address))
+    }
+
+    pub async fn get_registered_vk_list(&self) -> Result<AtHeight<Vec<String>>> {
+        Ok(self
+            .registered_vks()
+            .await?
+            .map(|vks| vks.iter().map(hex::encode).collect()))
+    }
+
+    pub async fn get_vk_registry(&self) -> Result<AtHeight<VkRegistry>> {
+        Ok(self
+            .registered_vks()
+            .await?
+            .map(|vks| VkRegistry::new(vks.into_iter().map(Vk::new))))
+    }
+
+    /// Fetches every page.
+    async fn registered_vks(&self) -> Result<AtHeight<Vec<Vec<u8>>>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let mut registered_vk_list = vec![];
+        let mut height = None;
+        let mut next_key = vec![];
+        loop {
+            let pagination = Some(PageRequest {
+                key: next_key,
+                ..Default::default()
+            });
+            let resp = client
+                .registered_vk_list(
+                    self.height
+                        .for_pages(height)
+                        .request(QueryRegisteredVkListRequest { pagination }),
+                )
+                .await?;
+            height = height.or(served_height(&resp));
+            let resp = resp.into_inner();
+            registered_vk_list.extend(resp.registered_vk_list);
+            next_key = resp
+                .pagination
+                .map(|page| page.next_key)
+                .unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(AtHeight {
+                    value: registered_vk_list,
+                    height,
+                });
+            }
+        }
     }
 }
 
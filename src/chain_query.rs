@@ -0,0 +1,367 @@
+#![allow(dead_code)]
+
+use crate::{
+    decoder::{self, DecodedTx},
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
+};
+use cosmos_sdk_proto::cosmos::base::{
+    query::v1beta1::PageRequest,
+    tendermint::v1beta1::{
+        service_client::ServiceClient, Block as SdkBlock, GetBlockByHeightRequest,
+        GetLatestBlockRequest, GetLatestValidatorSetRequest, GetNodeInfoRequest, GetSyncingRequest,
+        GetValidatorSetByHeightRequest,
+    },
+};
+use cosmrs::{
+    proto::tendermint::v0_34::types::{Block, BlockId},
+    AccountId, Any, ErrorReport, Result,
+};
+use prost_types::Timestamp;
+use std::{str::FromStr, time::SystemTime};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockInfo {
+    pub height: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::option_rfc3339")
+    )]
+    pub time: Option<SystemTime>,
+    /// Upper case hex.
+    pub hash: String,
+    pub chain_id: String,
+    /// Upper case hex, as in CometBFT headers. Nodes that return `sdk_block` send a bech32
+    /// consensus address, which is decoded.
+    pub proposer_address: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes_vec"))]
+    pub txs: Vec<Vec<u8>>,
+}
+
+impl BlockInfo {
+    /// Builds a block from the parts of a `GetLatestBlock` or `GetBlockByHeight` response,
+    /// preferring `sdk_block` and falling back to the deprecated `block`.
+    pub(crate) fn from_parts(
+        block_id: Option<BlockId>,
+        block: Option<Block>,
+        sdk_block: Option<SdkBlock>,
+    ) -> Result<Self> {
+        let hash = block_id
+            .map(|id| hex::encode_upper(id.hash))
+            .unwrap_or_default();
+        let (header, txs) = match (sdk_block, block) {
+            (
+                Some(SdkBlock {
+                    header: Some(header),
+                    data,
+                    ..
+                }),
+                _,
+            ) => (
+                (
+                    header.height,
+                    header.time.and_then(|time| SystemTime::try_from(time).ok()),
+                    header.chain_id,
+                    proposer_address_hex(&header.proposer_address)?,
+                ),
+                data,
+            ),
+            (
+                _,
+                Some(Block {
+                    header: Some(header),
+                    data,
+                    ..
+                }),
+            ) => (
+                (
+                    header.height,
+                    header.time.and_then(|time| {
+                        SystemTime::try_from(Timestamp {
+                            seconds: time.seconds,
+                            nanos: time.nanos,
+                        })
+                        .ok()
+                    }),
+                    header.chain_id,
+                    hex::encode_upper(header.proposer_address),
+                ),
+                data,
+            ),
+            _ => return Err(ErrorReport::msg("Empty block header in response")),
+        };
+        let (height, time, chain_id, proposer_address) = header;
+        Ok(Self {
+            height: u64::try_from(height)?,
+            time,
+            hash,
+            chain_id,
+            proposer_address,
+            txs: txs.map(|data| data.txs).unwrap_or_default(),
+        })
+    }
+
+    pub fn decoded_txs(&self) -> Result<Vec<DecodedTx>> {
+        decoder::decode_txs(&self.txs)
+    }
+}
+
+fn proposer_address_hex(address: &str) -> Result<String> {
+    if address.is_empty() {
+        return Ok(String::new());
+    }
+    let address = AccountId::from_str(address)
+        .map_err(|e| ErrorReport::msg(format!("Invalid proposer address {}: {}", address, e)))?;
+    Ok(hex::encode_upper(address.to_bytes()))
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeInfo {
+    pub node_id: String,
+    /// The chain ID.
+    pub network: String,
+    pub moniker: String,
+    pub tendermint_version: String,
+    pub app_name: String,
+    pub app_version: String,
+    pub git_commit: String,
+    pub cosmos_sdk_version: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorInfo {
+    /// Bech32 consensus address.
+    pub address: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::option_any"))]
+    pub pub_key: Option<Any>,
+    pub voting_power: i64,
+    pub proposer_priority: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorSet {
+    pub block_height: u64,
+    pub validators: Vec<ValidatorInfo>,
+}
+
+impl ValidatorSet {
+    pub fn total_voting_power(&self) -> i64 {
+        self.validators.iter().map(|v| v.voting_power).sum()
+    }
+}
+
+/// Block and node status queries over `cosmos.base.tendermint.v1beta1.Service`.
+#[derive(Debug, Clone)]
+pub struct ChainClient {
+    nodes: NodePool,
+    retry_policy: RetryPolicy,
+}
+
+impl ChainClient {
+    pub fn new(rpc: &str) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]))
+    }
+
+    /// A client that routes queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool) -> Self {
+        Self {
+            nodes,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.nodes.channel(&self.retry_policy)
+    }
+
+    pub async fn get_latest_block(&self) -> Result<BlockInfo> {
+        let mut client = ServiceClient::new(self.channel());
+        let resp = client
+            .get_latest_block(GetLatestBlockRequest {})
+            .await?
+            .into_inner();
+        BlockInfo::from_parts(resp.block_id, resp.block, resp.sdk_block)
+    }
+
+    pub async fn get_latest_height(&self) -> Result<u64> {
+        Ok(self.get_latest_block().await?.height)
+    }
+
+    pub async fn get_block_by_height(&self, height: u64) -> Result<BlockInfo> {
+        let mut client = ServiceClient::new(self.channel());
+        let resp = client
+            .get_block_by_height(GetBlockByHeightRequest {
+                height: i64::try_from(height)?,
+            })
+            .await?
+            .into_inner();
+        BlockInfo::from_parts(resp.block_id, resp.block, resp.sdk_block)
+    }
+
+    pub async fn get_node_info(&self) -> Result<NodeInfo> {
+        let mut client = ServiceClient::new(self.channel());
+        let resp = client
+            .get_node_info(GetNodeInfoRequest {})
+            .await?
+            .into_inner();
+        let mut node_info = NodeInfo::default();
+        if let Some(info) = resp.default_node_info {
+            node_info.node_id = info.default_node_id;
+            node_info.network = info.network;
+            node_info.moniker = info.moniker;
+            node_info.tendermint_version = info.version;
+        }
+        if let Some(version) = resp.application_version {
+            node_info.app_name = version.app_name;
+            node_info.app_version = version.version;
+            node_info.git_commit = version.git_commit;
+            node_info.cosmos_sdk_version = version.cosmos_sdk_version;
+        }
+        Ok(node_info)
+    }
+
+    /// Whether the node is still catching up with the chain.
+    pub async fn get_syncing(&self) -> Result<bool> {
+        let mut client = ServiceClient::new(self.channel());
+        let resp = client.get_syncing(GetSyncingRequest {}).await?;
+        Ok(resp.get_ref().syncing)
+    }
+
+    /// The validator set at `height`, or the latest one if `height` is `None`. Fetches every
+    /// page, all at the height the first page was served at.
+    pub async fn get_validator_set(&self, height: Option<u64>) -> Result<ValidatorSet> {
+        let mut client = ServiceClient::new(self.channel());
+        let mut height = height;
+        let mut validators = vec![];
+        let mut next_key = vec![];
+        loop {
+            let pagination = Some(PageRequest {
+                key: next_key,
+                ..Default::default()
+            });
+            let (block_height, page, page_response) = match height {
+                Some(height) => {
+                    let resp = client
+                        .get_validator_set_by_height(GetValidatorSetByHeightRequest {
+                            height: i64::try_from(height)?,
+                            pagination,
+                        })
+                        .await?
+                        .into_inner();
+                    (resp.block_height, resp.validators, resp.pagination)
+                }
+                None => {
+                    let resp = client
+                        .get_latest_validator_set(GetLatestValidatorSetRequest { pagination })
+                        .await?
+                        .into_inner();
+                    (resp.block_height, resp.validators, resp.pagination)
+                }
+            };
+            validators.extend(page.into_iter().map(|validator| ValidatorInfo {
+                address: validator.address,
+                pub_key: validator.pub_key,
+                voting_power: validator.voting_power,
+                proposer_priority: validator.proposer_priority,
+            }));
+            let block_height = u64::try_from(block_height)?;
+            next_key = page_response.map(|page| page.next_key).unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(ValidatorSet {
+                    block_height,
+                    validators,
+                });
+            }
+            // Later pages of the latest set must come from the block the first page did.
+            height.get_or_insert(block_height);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockInfo, ChainClient};
+    use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{Block as SdkBlock, Header};
+    use cosmrs::proto::tendermint::v0_34::types::{
+        Block, BlockId, Data, Header as TendermintHeader,
+    };
+    use cosmrs::AccountId;
+
+    const NODE: &str = "http://54.65.75.57:9090";
+
+    #[test]
+    fn block_info_from_parts() {
+        let block_id = Some(BlockId {
+            hash: vec![0xab; 32],
+            part_set_header: None,
+        });
+        let data = Some(Data {
+            txs: vec![vec![1, 2, 3]],
+        });
+        let sdk_block = SdkBlock {
+            header: Some(Header {
+                chain_id: "fiamma-testnet-1".to_string(),
+                height: 42,
+                proposer_address: AccountId::new("fiammavalcons", &[0x01, 0x02])
+                    .unwrap()
+                    .to_string(),
+                ..Default::default()
+            }),
+            data: data.clone(),
+            ..Default::default()
+        };
+        let block = Block {
+            header: Some(TendermintHeader {
+                chain_id: "fiamma-testnet-1".to_string(),
+                height: 42,
+                proposer_address: vec![0x01, 0x02],
+                ..Default::default()
+            }),
+            data,
+            ..Default::default()
+        };
+
+        let info = BlockInfo::from_parts(
+            block_id.clone(),
+            Some(block.clone()),
+            Some(sdk_block.clone()),
+        )
+        .unwrap();
+        assert_eq!(info.height, 42);
+        assert_eq!(info.hash, "AB".repeat(32));
+        assert_eq!(info.proposer_address, "0102");
+        assert_eq!(info.txs, vec![vec![1, 2, 3]]);
+
+        let info = BlockInfo::from_parts(block_id, Some(block), None).unwrap();
+        assert_eq!(info.proposer_address, "0102");
+        assert!(BlockInfo::from_parts(None, None, None).is_err());
+
+        let mut invalid = sdk_block;
+        invalid.header.as_mut().unwrap().proposer_address = "not-bech32".to_string();
+        assert!(BlockInfo::from_parts(None, None, Some(invalid)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_block() {
+        let client = ChainClient::new(NODE);
+        let block = client.get_latest_block().await;
+        println!("latest block: {:?}", block);
+        let node_info = client.get_node_info().await;
+        println!("node info: {:?}", node_info);
+    }
+}
@@ -0,0 +1,302 @@
+//! Tooling for BitVM challengers. Requires the `local-verifier` feature.
+//!
+//! The chain's proto does not document the layout of `BitVmChallengeData::witness`. This
+//! module assumes it is the compressed Groth16 proof followed by its public input, in the
+//! same encodings as `proof.bitvm` and `public_input.bitvm`, and has only been checked
+//! against witnesses built that way, not against witnesses produced by the chain or the
+//! BitVM disprove scripts. A witness that does not fit this layout is an error, never a
+//! fraud. [`find_fraud`] replays the verifier one step at a time and reports the first step
+//! that fails.
+
+use crate::{
+    types::BitVmChallengeData,
+    validation::{
+        FR_SIZE, G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE, GROTH16_PROOF_SIZE, LENGTH_PREFIX_SIZE,
+    },
+    zkpverify_query::QueryClient,
+};
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use cosmrs::{ErrorReport, Result};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// The Groth16 verifier steps, in execution order. The discriminant is this SDK's own
+/// numbering; map it to the step indices of your disprove script before using it there.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum ChallengeStep {
+    /// `A` is not a valid compressed G1 point in the prime-order subgroup.
+    ProofA = 0,
+    /// `B` is not a valid compressed G2 point in the prime-order subgroup.
+    ProofB = 1,
+    /// `C` is not a valid compressed G1 point in the prime-order subgroup.
+    ProofC = 2,
+    /// The public input is malformed or contains a non-canonical field element.
+    PublicInput = 3,
+    /// The public input count does not match the VK, so `vk_x` cannot be computed.
+    InputAccumulation = 4,
+    /// `e(A, B) != e(alpha, beta) * e(vk_x, gamma) * e(C, delta)`.
+    PairingCheck = 5,
+}
+
+impl ChallengeStep {
+    pub fn index(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ChallengeStep::ProofA => "PROOF_A",
+            ChallengeStep::ProofB => "PROOF_B",
+            ChallengeStep::ProofC => "PROOF_C",
+            ChallengeStep::PublicInput => "PUBLIC_INPUT",
+            ChallengeStep::InputAccumulation => "INPUT_ACCUMULATION",
+            ChallengeStep::PairingCheck => "PAIRING_CHECK",
+        }
+    }
+}
+
+impl fmt::Display for ChallengeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str_name())
+    }
+}
+
+/// A BitVM witness split into its components.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitVmWitness {
+    pub proof_a: Vec<u8>,
+    pub proof_b: Vec<u8>,
+    pub proof_c: Vec<u8>,
+    pub public_input: Vec<u8>,
+}
+
+impl BitVmWitness {
+    /// Splits `witness` into a compressed Groth16 proof and a length-prefixed public input,
+    /// failing if its length does not match that layout. The points and field elements are
+    /// not checked here.
+    pub fn decode(witness: &[u8]) -> Result<Self> {
+        let min_size = GROTH16_PROOF_SIZE + LENGTH_PREFIX_SIZE;
+        if witness.len() < min_size {
+            return Err(ErrorReport::msg(format!(
+                "BitVM witness is {} bytes, shorter than a {} byte groth16 proof and public \
+                 input count",
+                witness.len(),
+                min_size
+            )));
+        }
+        let (proof_a, rest) = witness.split_at(G1_COMPRESSED_SIZE);
+        let (proof_b, rest) = rest.split_at(G2_COMPRESSED_SIZE);
+        let (proof_c, public_input) = rest.split_at(G1_COMPRESSED_SIZE);
+        let count = u64::from_le_bytes(
+            public_input[..LENGTH_PREFIX_SIZE]
+                .try_into()
+                .expect("prefix is 8 bytes"),
+        );
+        let expected = (count as u128) * (FR_SIZE as u128) + LENGTH_PREFIX_SIZE as u128;
+        if public_input.len() as u128 != expected {
+            return Err(ErrorReport::msg(format!(
+                "BitVM witness public input is {} bytes, but its count of {} needs {}; the \
+                 witness does not use the proof || public_input layout",
+                public_input.len(),
+                count,
+                expected
+            )));
+        }
+        Ok(Self {
+            proof_a: proof_a.to_vec(),
+            proof_b: proof_b.to_vec(),
+            proof_c: proof_c.to_vec(),
+            public_input: public_input.to_vec(),
+        })
+    }
+
+    pub fn proof(&self) -> Vec<u8> {
+        [self.proof_a.as_slice(), &self.proof_b, &self.proof_c].concat()
+    }
+}
+
+/// Evidence that a proposer asserted a proof which does not verify.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeReport {
+    pub proof_id: String,
+    pub proposer: String,
+    pub step: ChallengeStep,
+    pub reason: String,
+    pub witness: Vec<u8>,
+    pub vk: Vec<u8>,
+}
+
+impl ChallengeReport {
+    /// The report as JSON, with bytes hex encoded and `step` as [`ChallengeStep::index`].
+    pub fn to_json(&self) -> Value {
+        json!({
+            "proof_id": self.proof_id,
+            "proposer": self.proposer,
+            "step": self.step.index(),
+            "step_name": self.step.as_str_name(),
+            "reason": self.reason,
+            "witness": hex::encode(&self.witness),
+            "vk": hex::encode(&self.vk),
+        })
+    }
+}
+
+/// Replays the Groth16 verifier over `challenge_data`, returning the first failing step and
+/// why it failed, or `None` if the assertion is honest. An undecodable VK or a witness that
+/// [`BitVmWitness::decode`] rejects is an error rather than a fraud, as it more likely means
+/// the assumed layout is wrong than that the proposer cheated.
+pub fn find_fraud(challenge_data: &BitVmChallengeData) -> Result<Option<(ChallengeStep, String)>> {
+    let vk = VerifyingKey::<Bn254>::deserialize_compressed(challenge_data.vk.as_slice())
+        .map_err(|e| ErrorReport::msg(format!("Failed to parse groth16 vk: {}", e)))?;
+    let witness = BitVmWitness::decode(&challenge_data.witness)?;
+
+    let a = match G1Affine::deserialize_compressed(witness.proof_a.as_slice()) {
+        Ok(a) => a,
+        Err(e) => return Ok(Some((ChallengeStep::ProofA, e.to_string()))),
+    };
+    let b = match G2Affine::deserialize_compressed(witness.proof_b.as_slice()) {
+        Ok(b) => b,
+        Err(e) => return Ok(Some((ChallengeStep::ProofB, e.to_string()))),
+    };
+    let c = match G1Affine::deserialize_compressed(witness.proof_c.as_slice()) {
+        Ok(c) => c,
+        Err(e) => return Ok(Some((ChallengeStep::ProofC, e.to_string()))),
+    };
+    let public_input = match Vec::<Fr>::deserialize_compressed(witness.public_input.as_slice()) {
+        Ok(public_input) => public_input,
+        Err(e) => return Ok(Some((ChallengeStep::PublicInput, e.to_string()))),
+    };
+
+    if vk.gamma_abc_g1.len() != public_input.len() + 1 {
+        return Ok(Some((
+            ChallengeStep::InputAccumulation,
+            format!(
+                "vk expects {} public inputs, witness has {}",
+                vk.gamma_abc_g1.len().saturating_sub(1),
+                public_input.len()
+            ),
+        )));
+    }
+    let pvk = prepare_verifying_key(&vk);
+    let vk_x = Groth16::<Bn254>::prepare_inputs(&pvk, &public_input)
+        .map_err(|e| ErrorReport::msg(format!("Failed to accumulate public input: {}", e)))?;
+
+    let proof = Proof::<Bn254> { a, b, c };
+    let verified = Groth16::<Bn254>::verify_proof_with_prepared_inputs(&pvk, &proof, &vk_x)
+        .map_err(|e| ErrorReport::msg(format!("Failed to run pairing check: {}", e)))?;
+    if !verified {
+        return Ok(Some((
+            ChallengeStep::PairingCheck,
+            "pairing equation does not hold".to_string(),
+        )));
+    }
+    Ok(None)
+}
+
+#[derive(Debug, Clone)]
+pub struct Challenger {
+    query_client: QueryClient,
+}
+
+impl Challenger {
+    pub fn new(rpc: &str) -> Self {
+        Self {
+            query_client: QueryClient::new(rpc),
+        }
+    }
+
+    /// Fetches the BitVM challenge data for `proof_id` and checks it, returning a report if
+    /// the proposer's assertion can be disproved.
+    pub async fn check(&self, proof_id: &str) -> Result<Option<ChallengeReport>> {
+        let challenge_data = self.query_client.get_bitvm_challenge_data(proof_id).await?;
+        Ok(
+            find_fraud(&challenge_data)?.map(|(step, reason)| ChallengeReport {
+                proof_id: proof_id.to_string(),
+                proposer: challenge_data.proposer,
+                step,
+                reason,
+                witness: challenge_data.witness,
+                vk: challenge_data.vk,
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_fraud, BitVmWitness, ChallengeStep};
+    use crate::types::BitVmChallengeData;
+
+    const TEST_DATA: &str = "test-data";
+
+    fn challenge_data() -> BitVmChallengeData {
+        let location = std::env::current_dir().unwrap().join(TEST_DATA);
+        let proof = std::fs::read(location.join("proof.bitvm")).unwrap();
+        let public_input = std::fs::read(location.join("public_input.bitvm")).unwrap();
+        let vk = std::fs::read(location.join("vk.bitvm")).unwrap();
+        BitVmChallengeData {
+            witness: [proof, public_input].concat(),
+            vk,
+            proposer: "fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp".to_string(),
+        }
+    }
+
+    fn fraud_step(challenge_data: &BitVmChallengeData) -> Option<ChallengeStep> {
+        find_fraud(challenge_data).unwrap().map(|(step, _)| step)
+    }
+
+    #[test]
+    fn decode_witness() {
+        let challenge_data = challenge_data();
+        let witness = BitVmWitness::decode(&challenge_data.witness).unwrap();
+        assert_eq!(witness.proof_a.len(), 32);
+        assert_eq!(witness.proof_b.len(), 64);
+        assert_eq!(witness.proof_c.len(), 32);
+        assert_eq!(
+            [witness.proof(), witness.public_input].concat(),
+            challenge_data.witness
+        );
+    }
+
+    #[test]
+    fn undecodable_witness_is_an_error() {
+        let mut short = challenge_data();
+        short.witness.truncate(100);
+        assert!(find_fraud(&short).is_err());
+
+        let mut trailing = challenge_data();
+        trailing.witness.push(0);
+        assert!(find_fraud(&trailing).is_err());
+
+        let mut wrong_count = challenge_data();
+        wrong_count.witness[128] = 2;
+        assert!(find_fraud(&wrong_count).is_err());
+    }
+
+    #[test]
+    fn honest_assertion() {
+        assert_eq!(fraud_step(&challenge_data()), None);
+    }
+
+    #[test]
+    fn fraudulent_assertions() {
+        let mut invalid_point = challenge_data();
+        invalid_point.witness[..32].fill(0xff);
+        assert_eq!(fraud_step(&invalid_point), Some(ChallengeStep::ProofA));
+
+        let mut wrong_input_count = challenge_data();
+        wrong_input_count.witness[128] = 2;
+        wrong_input_count.witness.extend([0; 32]);
+        assert_eq!(
+            fraud_step(&wrong_input_count),
+            Some(ChallengeStep::InputAccumulation)
+        );
+
+        let mut wrong_input = challenge_data();
+        wrong_input.witness[136] ^= 1;
+        assert_eq!(fraud_step(&wrong_input), Some(ChallengeStep::PairingCheck));
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{
+    error_hook::ErrorHook,
+    tx::TxClient,
+    types::{MsgSubmitCommunityVerification, ProofData, VerifyResult},
+    zkpverify_query::QueryClient,
+};
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use cosmrs::{ErrorReport, Result};
+use std::{
+    collections::HashSet,
+    fs::{self, OpenOptions},
+    future::Future,
+    io::Write,
+    path::PathBuf,
+    time::Duration,
+};
+
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+pub const DEFAULT_BATCH_SIZE: usize = 10;
+pub const DEFAULT_INCLUSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Watches pending proofs and submits community verification votes for them.
+///
+/// `verify` is called with the proof ID and its [`ProofData`] and returns the vote. Proofs
+/// whose callback fails are left unvoted and retried on the next poll. Votes are batched
+/// into one transaction of up to `batch_size` messages, and proof IDs are only recorded as
+/// voted once the transaction executed successfully in a block. If the chain rejects a batch,
+/// its votes are resent one per transaction so a single rejected vote does not hold back the
+/// rest; rejected votes are reported and retried on the next poll. Failed polls, callbacks and
+/// rejected votes are passed to the [`ErrorHook`] set with
+/// [`CommunityVerifier::with_error_hook`].
+pub struct CommunityVerifier<F> {
+    tx_client: TxClient,
+    query_client: QueryClient,
+    verify: F,
+    namespace: Option<String>,
+    poll_interval: Duration,
+    batch_size: usize,
+    inclusion_timeout: Duration,
+    voted: HashSet<String>,
+    voted_store: Option<PathBuf>,
+    error_hook: ErrorHook,
+}
+
+impl<F, Fut> CommunityVerifier<F>
+where
+    F: Fn(String, ProofData) -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    pub fn new(tx_client: TxClient, verify: F) -> Self {
+        let error_hook = tx_client.error_hook.clone();
+        let query_client = QueryClient::from_nodes(tx_client.nodes.clone())
+            .with_retry_policy(tx_client.retry_policy.clone());
+        Self {
+            tx_client,
+            query_client,
+            verify,
+            namespace: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            batch_size: DEFAULT_BATCH_SIZE,
+            inclusion_timeout: DEFAULT_INCLUSION_TIMEOUT,
+            voted: HashSet::new(),
+            voted_store: None,
+            error_hook,
+        }
+    }
+
+    /// Only vote on proofs submitted under `namespace`.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// How long to wait for a vote transaction to be included in a block before the poll
+    /// fails and its votes are retried.
+    pub fn with_inclusion_timeout(mut self, inclusion_timeout: Duration) -> Self {
+        self.inclusion_timeout = inclusion_timeout;
+        self
+    }
+
+    /// Persist voted proof IDs to `path`, one per line, loading any IDs already there so a
+    /// restarted verifier does not vote twice.
+    pub fn with_voted_store(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            let contents = fs::read_to_string(&path)?;
+            self.voted.extend(
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string),
+            );
+        }
+        self.voted_store = Some(path);
+        Ok(self)
+    }
+
+    /// Receives poll failures from [`CommunityVerifier::run`] and proofs whose `verify`
+    /// callback failed. Defaults to the hook of the [`TxClient`].
+    pub fn with_error_hook(mut self, error_hook: ErrorHook) -> Self {
+        self.error_hook = error_hook;
+        self
+    }
+
+    pub fn has_voted(&self, proof_id: &str) -> bool {
+        self.voted.contains(proof_id)
+    }
+
+    /// Polls until `shutdown` resolves. A poll in progress is always finished, so no vote is
+    /// broadcast without being recorded.
+    pub async fn run(&mut self, shutdown: impl Future<Output = ()>) -> Result<()> {
+        tokio::pin!(shutdown);
+        loop {
+            if let Err(e) = self.poll_once().await {
+                self.error_hook.report(&ErrorReport::msg(format!(
+                    "Community verifier poll failed: {}",
+                    e
+                )));
+            }
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+    }
+
+    /// Votes on every pending proof not yet voted on, returning the number of votes submitted.
+    pub async fn poll_once(&mut self) -> Result<usize> {
+        let pending = self
+            .query_client
+            .pending_proofs(self.namespace.as_deref())
+            .await?;
+        let proof_ids = self.unvoted(pending);
+
+        let mut submitted = 0;
+        for chunk in proof_ids.chunks(self.batch_size) {
+            let mut votes = Vec::with_capacity(chunk.len());
+            for proof_id in chunk {
+                match self.vote(proof_id).await {
+                    Ok(vote) => votes.push(vote),
+                    Err(e) => self.error_hook.report(&ErrorReport::msg(format!(
+                        "Failed to verify proof {}: {}",
+                        proof_id, e
+                    ))),
+                }
+            }
+            if votes.is_empty() {
+                continue;
+            }
+
+            if let Some(reason) = self.submit_votes(votes.clone()).await? {
+                if votes.len() == 1 {
+                    self.report_rejected(&votes[0].proof_id, &reason);
+                    continue;
+                }
+                for vote in votes {
+                    let proof_id = vote.proof_id.clone();
+                    match self.submit_votes(vec![vote]).await? {
+                        Some(reason) => self.report_rejected(&proof_id, &reason),
+                        None => {
+                            self.record_voted(&[proof_id])?;
+                            submitted += 1;
+                        }
+                    }
+                }
+                continue;
+            }
+            let proof_ids: Vec<String> = votes.into_iter().map(|vote| vote.proof_id).collect();
+            self.record_voted(&proof_ids)?;
+            submitted += proof_ids.len();
+        }
+        Ok(submitted)
+    }
+
+    /// Broadcasts `votes` in one transaction and waits for it to be included, returning the
+    /// chain's reason if it rejected them at `CheckTx` or in the block.
+    async fn submit_votes(
+        &self,
+        votes: Vec<MsgSubmitCommunityVerification>,
+    ) -> Result<Option<String>> {
+        let resp = self.tx_client.submit_community_verifications(votes).await?;
+        let tx_response = resp
+            .tx_response
+            .ok_or(ErrorReport::msg("Empty tx response while submitting votes"))?;
+        if tx_response.code != 0 {
+            return Ok(Some(rejection(&tx_response)));
+        }
+        let tx_response = self
+            .tx_client
+            .wait_for_tx(&tx_response.txhash, self.inclusion_timeout)
+            .await?;
+        Ok((tx_response.code != 0).then(|| rejection(&tx_response)))
+    }
+
+    fn report_rejected(&self, proof_id: &str, reason: &str) {
+        self.error_hook.report(&ErrorReport::msg(format!(
+            "Community verification of proof {} was rejected: {}",
+            proof_id, reason
+        )));
+    }
+
+    async fn vote(&self, proof_id: &str) -> Result<MsgSubmitCommunityVerification> {
+        let proof_data = self.query_client.get_proof_data(proof_id).await?;
+        let verify_result = (self.verify)(proof_id.to_string(), proof_data).await?;
+        Ok(MsgSubmitCommunityVerification {
+            creator: self.tx_client.account_id(),
+            proof_id: proof_id.to_string(),
+            verify_result,
+        })
+    }
+
+    fn unvoted(&self, pending: Vec<VerifyResult>) -> Vec<String> {
+        let mut seen = HashSet::new();
+        pending
+            .into_iter()
+            .map(|result| result.proof_id)
+            .filter(|proof_id| !self.voted.contains(proof_id) && seen.insert(proof_id.clone()))
+            .collect()
+    }
+
+    fn record_voted(&mut self, proof_ids: &[String]) -> Result<()> {
+        if let Some(path) = &self.voted_store {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            for proof_id in proof_ids {
+                writeln!(file, "{}", proof_id)?;
+            }
+        }
+        self.voted.extend(proof_ids.iter().cloned());
+        Ok(())
+    }
+}
+
+fn rejection(tx_response: &TxResponse) -> String {
+    format!(
+        "tx {} failed with code {}: {}",
+        tx_response.txhash, tx_response.code, tx_response.raw_log
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommunityVerifier;
+    use crate::{
+        error_hook::ErrorHook,
+        retry::RetryPolicy,
+        tx::TxClient,
+        types::{ProofData, VerifyResult},
+    };
+    use cosmrs::Result;
+    use std::sync::{Arc, Mutex};
+
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+
+    async fn accept(_proof_id: String, _proof_data: ProofData) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn pending(proof_id: &str) -> VerifyResult {
+        VerifyResult {
+            proof_id: proof_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn skips_voted_and_duplicate_proofs() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let mut verifier = CommunityVerifier::new(tx_client, accept);
+        verifier.record_voted(&["a".to_string()]).unwrap();
+        let unvoted =
+            verifier.unvoted(vec![pending("a"), pending("b"), pending("b"), pending("c")]);
+        assert_eq!(unvoted, vec!["b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn voted_store_survives_restart() {
+        let path = std::env::temp_dir().join(format!(
+            "fiamma-community-verifier-{}.txt",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let mut verifier = CommunityVerifier::new(tx_client.clone(), accept)
+            .with_voted_store(&path)
+            .unwrap();
+        verifier
+            .record_voted(&["a".to_string(), "b".to_string()])
+            .unwrap();
+
+        let restarted = CommunityVerifier::new(tx_client, accept)
+            .with_voted_store(&path)
+            .unwrap();
+        assert!(restarted.has_voted("a"));
+        assert!(restarted.has_voted("b"));
+        assert!(!restarted.has_voted("c"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn poll_fails_without_node() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let mut verifier = CommunityVerifier::new(tx_client, accept);
+        assert!(verifier.poll_once().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn run_reports_failed_polls() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let sink = errors.clone();
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000)
+            .with_retry_policy(RetryPolicy::none());
+        let mut verifier =
+            CommunityVerifier::new(tx_client, accept).with_error_hook(ErrorHook::new(move |e| {
+                sink.lock().unwrap().push(e.to_string())
+            }));
+        verifier.run(async {}).await.unwrap();
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Community verifier poll failed"));
+    }
+}
@@ -0,0 +1,660 @@
+use crate::{
+    error_hook::ErrorHook,
+    node_pool::NodePool,
+    retry::RetryPolicy,
+    tx::TxClient,
+    types::{
+        msg_submit_proof::hash_proof_id, DaSubmissionData, DaSubmissionResult, DataLocation,
+        MsgUpdateDASubmissionResults, ProofData, ProofSystem,
+    },
+    zkpverify_query::QueryClient,
+};
+use cosmrs::{proto::traits::Message, ErrorReport, Result};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap, fmt, fs, future::Future, path::PathBuf, sync::Mutex, time::Duration,
+};
+
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+pub const DEFAULT_BATCH_SIZE: usize = 10;
+
+/// A proof as posted to a DA layer: the protobuf encoded `ProofData`, under the proof's
+/// namespace.
+///
+/// Neither the chain nor the DA layers define how a Fiamma proof is laid out in a blob, so
+/// this encoding is the SDK's own convention. [`verify_blob`] expects it, and can only audit
+/// blobs posted by relayers that use it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaBlob {
+    pub proof_id: String,
+    pub namespace: String,
+    pub data: Vec<u8>,
+}
+
+impl DaBlob {
+    pub fn from_submission(submission: &DaSubmissionData) -> Result<Self> {
+        let proof_data = submission
+            .proof_data
+            .as_ref()
+            .ok_or(ErrorReport::msg("Empty proof data in DA submission"))?;
+        Ok(Self {
+            proof_id: submission.proof_id.clone(),
+            namespace: proof_data.namespace.clone(),
+            data: proof_data.encode_to_vec(),
+        })
+    }
+}
+
+/// Where a DA layer included a blob.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaReceipt {
+    pub block_hash: String,
+    pub block_height: u64,
+    pub tx_hash: String,
+}
+
+/// A DA layer the relayer can post proofs to.
+pub trait DaBackend {
+    /// The `DataLocation` of the proofs this backend is responsible for.
+    fn data_location(&self) -> DataLocation;
+
+    fn submit(&self, blob: &DaBlob) -> impl Future<Output = Result<DaReceipt>> + Send;
+}
+
+/// Drains the chain's DA submission queue into a [`DaBackend`] and reports where each proof
+/// was posted with `MsgUpdateDASubmissionResults`. The client's account must be the
+/// chain's DA submitter.
+///
+/// Results are kept until they are reported, so a proof whose report tx fails is not posted
+/// again on the next poll; only the report is retried. With
+/// [`DaRelayer::with_posted_store`] they also survive a restart. Reports of several chunks
+/// are broadcast back to back, relying on [`TxClient`] to sign each with the next sequence.
+/// Failed polls and posts are passed to the [`ErrorHook`] set with
+/// [`DaRelayer::with_error_hook`].
+pub struct DaRelayer<B> {
+    tx_client: TxClient,
+    query_client: QueryClient,
+    backend: B,
+    poll_interval: Duration,
+    batch_size: usize,
+    posted: Mutex<HashMap<String, DaSubmissionResult>>,
+    posted_store: Option<PathBuf>,
+    error_hook: ErrorHook,
+}
+
+impl<B: DaBackend> DaRelayer<B> {
+    pub fn new(tx_client: TxClient, backend: B) -> Self {
+        let query_client = QueryClient::from_nodes(tx_client.nodes.clone())
+            .with_retry_policy(tx_client.retry_policy.clone());
+        let error_hook = tx_client.error_hook.clone();
+        Self {
+            tx_client,
+            query_client,
+            backend,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            batch_size: DEFAULT_BATCH_SIZE,
+            posted: Mutex::new(HashMap::new()),
+            posted_store: None,
+            error_hook,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Persist posted but unreported results to `path`, one hex encoded
+    /// `DASubmissionResult` per line, loading any already there so a restarted relayer
+    /// reports them instead of posting the proofs again.
+    pub fn with_posted_store(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            let posted = self.posted.get_mut().unwrap();
+            for line in fs::read_to_string(&path)?.lines().map(str::trim) {
+                if line.is_empty() {
+                    continue;
+                }
+                let result = DaSubmissionResult::decode(hex::decode(line)?.as_slice())?;
+                posted.insert(result.proof_id.clone(), result);
+            }
+        }
+        self.posted_store = Some(path);
+        Ok(self)
+    }
+
+    /// Receives poll failures from [`DaRelayer::run`] and proofs that could not be posted.
+    /// Defaults to the hook of the [`TxClient`].
+    pub fn with_error_hook(mut self, error_hook: ErrorHook) -> Self {
+        self.error_hook = error_hook;
+        self
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Relays until `shutdown` resolves. A poll in progress is always finished, so a posted
+    /// blob is not left unreported.
+    pub async fn run(&self, shutdown: impl Future<Output = ()>) -> Result<()> {
+        tokio::pin!(shutdown);
+        loop {
+            if let Err(e) = self.poll_once().await {
+                self.error_hook
+                    .report(&ErrorReport::msg(format!("DA relayer poll failed: {}", e)));
+            }
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+    }
+
+    /// Posts every queued proof for this backend's data location, returning the number of
+    /// results reported on-chain.
+    pub async fn poll_once(&self) -> Result<usize> {
+        let queue = self.query_client.get_da_submission_queue().await?;
+        let data_location = self.backend.data_location() as i32;
+        let submissions: Vec<DaSubmissionData> = queue
+            .into_iter()
+            .filter(|submission| {
+                submission
+                    .proof_data
+                    .as_ref()
+                    .is_some_and(|proof_data| proof_data.data_location == data_location)
+            })
+            .collect();
+        // Results of proofs that left the queue were reported, even if their tx response
+        // was lost.
+        {
+            let mut posted = self.posted.lock().unwrap();
+            posted.retain(|proof_id, _| {
+                submissions
+                    .iter()
+                    .any(|submission| &submission.proof_id == proof_id)
+            });
+            self.save_posted(&posted)?;
+        }
+
+        let mut reported = 0;
+        for chunk in submissions.chunks(self.batch_size) {
+            let mut results = Vec::with_capacity(chunk.len());
+            for submission in chunk {
+                match self.relay(submission).await {
+                    Ok(result) => results.push(result),
+                    Err(e) => self.error_hook.report(&ErrorReport::msg(format!(
+                        "Failed to post proof {}: {}",
+                        submission.proof_id, e
+                    ))),
+                }
+            }
+            if results.is_empty() {
+                continue;
+            }
+
+            let proof_ids: Vec<String> = results
+                .iter()
+                .map(|result| result.proof_id.clone())
+                .collect();
+            let resp = self
+                .tx_client
+                .update_da_submission_results(MsgUpdateDASubmissionResults {
+                    creator: self.tx_client.account_id(),
+                    da_submission_results: results,
+                })
+                .await?;
+            let tx_response = resp.tx_response.ok_or(ErrorReport::msg(
+                "Empty tx response while reporting DA results",
+            ))?;
+            if tx_response.code != 0 {
+                return Err(ErrorReport::msg(format!(
+                    "DA submission results tx {} failed with code {}: {}",
+                    tx_response.txhash, tx_response.code, tx_response.raw_log
+                )));
+            }
+            let mut posted = self.posted.lock().unwrap();
+            for proof_id in &proof_ids {
+                posted.remove(proof_id);
+            }
+            self.save_posted(&posted)?;
+            reported += proof_ids.len();
+        }
+        Ok(reported)
+    }
+
+    /// Posts `submission`, or returns its result if it was posted but not yet reported.
+    async fn relay(&self, submission: &DaSubmissionData) -> Result<DaSubmissionResult> {
+        if let Some(result) = self.posted.lock().unwrap().get(&submission.proof_id) {
+            return Ok(result.clone());
+        }
+        let blob = DaBlob::from_submission(submission)?;
+        let receipt = self.backend.submit(&blob).await?;
+        let result = DaSubmissionResult {
+            proof_id: blob.proof_id,
+            data_location: self.backend.data_location() as i32,
+            block_hash: receipt.block_hash,
+            block_height: receipt.block_height,
+            tx_hash: receipt.tx_hash,
+            namespace: blob.namespace,
+        };
+        let mut posted = self.posted.lock().unwrap();
+        posted.insert(result.proof_id.clone(), result.clone());
+        self.save_posted(&posted)?;
+        Ok(result)
+    }
+
+    /// Rewrites the posted store, if any, through a temporary file so a crash never leaves
+    /// it half written.
+    fn save_posted(&self, posted: &HashMap<String, DaSubmissionResult>) -> Result<()> {
+        let Some(path) = &self.posted_store else {
+            return Ok(());
+        };
+        let contents: String = posted
+            .values()
+            .map(|result| hex::encode(result.encode_to_vec()) + "\n")
+            .collect();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, contents)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// A DA layer proofs can be read back from, to check they were made available where the
+/// chain claims.
+pub trait DaReader {
+    fn data_location(&self) -> DataLocation;
+
+    /// Fetches the blob included at `result.block_height` in `result.tx_hash`.
+    fn read(&self, result: &DaSubmissionResult) -> impl Future<Output = Result<Vec<u8>>> + Send;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AvailabilityError {
+    WrongDataLocation { expected: i32, actual: i32 },
+    NamespaceMismatch { expected: String, actual: String },
+    UndecodableBlob(String),
+    ProofDataMismatch,
+    ProofIdMismatch { expected: String, actual: String },
+}
+
+impl fmt::Display for AvailabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AvailabilityError::WrongDataLocation { expected, actual } => write!(
+                f,
+                "proof was posted to data location {}, reader is for {}",
+                actual, expected
+            ),
+            AvailabilityError::NamespaceMismatch { expected, actual } => write!(
+                f,
+                "blob namespace {:?} does not match on-chain namespace {:?}",
+                actual, expected
+            ),
+            AvailabilityError::UndecodableBlob(reason) => {
+                write!(f, "blob is not a ProofData: {}", reason)
+            }
+            AvailabilityError::ProofDataMismatch => {
+                write!(f, "blob does not match the on-chain proof data")
+            }
+            AvailabilityError::ProofIdMismatch { expected, actual } => write!(
+                f,
+                "blob hashes to proof ID {}, expected {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AvailabilityError {}
+
+/// The chain's proof ID of `proof_data`, as [`MsgSubmitProof::proof_id`] computes it for
+/// the submitted message.
+///
+/// [`MsgSubmitProof::proof_id`]: crate::types::MsgSubmitProof::proof_id
+pub fn proof_id(proof_data: &ProofData) -> Result<String> {
+    let proof_system = ProofSystem::try_from(proof_data.proof_system).map_err(|_| {
+        ErrorReport::msg(format!("Unknown proof system {}", proof_data.proof_system))
+    })?;
+    Ok(hash_proof_id(
+        &proof_data.namespace,
+        proof_system.as_str_name(),
+        &proof_data.proof,
+        &proof_data.public_input,
+        &proof_data.vk,
+    ))
+}
+
+/// Checks that `blob`, read back from the DA layer named in `result`, is the on-chain
+/// `proof_data` and hashes to the claimed proof ID.
+pub fn verify_blob(
+    result: &DaSubmissionResult,
+    proof_data: &ProofData,
+    blob: &[u8],
+) -> Result<(), AvailabilityError> {
+    if result.data_location != proof_data.data_location {
+        return Err(AvailabilityError::WrongDataLocation {
+            expected: proof_data.data_location,
+            actual: result.data_location,
+        });
+    }
+    let posted =
+        ProofData::decode(blob).map_err(|e| AvailabilityError::UndecodableBlob(e.to_string()))?;
+    if posted.namespace != result.namespace {
+        return Err(AvailabilityError::NamespaceMismatch {
+            expected: result.namespace.clone(),
+            actual: posted.namespace,
+        });
+    }
+    if &posted != proof_data {
+        return Err(AvailabilityError::ProofDataMismatch);
+    }
+    let actual =
+        proof_id(&posted).map_err(|e| AvailabilityError::UndecodableBlob(e.to_string()))?;
+    if actual != result.proof_id {
+        return Err(AvailabilityError::ProofIdMismatch {
+            expected: result.proof_id.clone(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Audits DA submissions reported on-chain against a [`DaReader`].
+pub struct DaAuditor<R> {
+    query_client: QueryClient,
+    reader: R,
+}
+
+impl<R: DaReader> DaAuditor<R> {
+    pub fn new(rpc: &str, reader: R) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]), reader)
+    }
+
+    /// An auditor that routes chain queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool, reader: R) -> Self {
+        Self {
+            query_client: QueryClient::from_nodes(nodes),
+            reader,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.query_client = self.query_client.with_retry_policy(retry_policy);
+        self
+    }
+
+    /// Route chain queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.query_client = self.query_client.with_nodes(nodes);
+        self
+    }
+
+    /// Fetches `proof_id`'s DA submission result and proof data from the chain, reads the
+    /// blob back from the DA layer and checks it with [`verify_blob`].
+    pub async fn audit(&self, proof_id: &str) -> Result<()> {
+        let result = self.query_client.get_da_submission_result(proof_id).await?;
+        if result.data_location != self.reader.data_location() as i32 {
+            return Err(AvailabilityError::WrongDataLocation {
+                expected: self.reader.data_location() as i32,
+                actual: result.data_location,
+            }
+            .into());
+        }
+        let proof_data = self.query_client.get_proof_data(proof_id).await?;
+        let blob = self.reader.read(&result).await?;
+        verify_blob(&result, &proof_data, &blob)?;
+        Ok(())
+    }
+}
+
+/// A [`DaBackend`] that keeps blobs in memory, one blob per block. For tests.
+#[derive(Debug)]
+pub struct InMemoryBackend {
+    data_location: DataLocation,
+    blobs: Mutex<Vec<DaBlob>>,
+}
+
+impl InMemoryBackend {
+    pub fn new(data_location: DataLocation) -> Self {
+        Self {
+            data_location,
+            blobs: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn blobs(&self) -> Vec<DaBlob> {
+        self.blobs.lock().unwrap().clone()
+    }
+
+    fn receipt(block_height: u64, blob: &DaBlob) -> DaReceipt {
+        let block_hash = Sha256::new()
+            .chain_update(block_height.to_be_bytes())
+            .chain_update(&blob.data)
+            .finalize();
+        let tx_hash = Sha256::new()
+            .chain_update(&blob.namespace)
+            .chain_update(&blob.data)
+            .finalize();
+        DaReceipt {
+            block_hash: hex::encode(block_hash),
+            block_height,
+            tx_hash: hex::encode(tx_hash),
+        }
+    }
+}
+
+impl DaBackend for InMemoryBackend {
+    fn data_location(&self) -> DataLocation {
+        self.data_location
+    }
+
+    async fn submit(&self, blob: &DaBlob) -> Result<DaReceipt> {
+        let mut blobs = self.blobs.lock().unwrap();
+        blobs.push(blob.clone());
+        Ok(Self::receipt(blobs.len() as u64, blob))
+    }
+}
+
+impl DaReader for InMemoryBackend {
+    fn data_location(&self) -> DataLocation {
+        self.data_location
+    }
+
+    async fn read(&self, result: &DaSubmissionResult) -> Result<Vec<u8>> {
+        let blobs = self.blobs.lock().unwrap();
+        let blob = result
+            .block_height
+            .checked_sub(1)
+            .and_then(|index| blobs.get(index as usize))
+            .ok_or(ErrorReport::msg(format!(
+                "No blob at height {}",
+                result.block_height
+            )))?;
+        if Self::receipt(result.block_height, blob).tx_hash != result.tx_hash {
+            return Err(ErrorReport::msg(format!(
+                "No blob with tx hash {} at height {}",
+                result.tx_hash, result.block_height
+            )));
+        }
+        Ok(blob.data.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        proof_id, verify_blob, AvailabilityError, DaAuditor, DaBackend, DaBlob, DaReader,
+        DaRelayer, InMemoryBackend,
+    };
+    use crate::{
+        node_pool::NodePool,
+        retry::RetryPolicy,
+        tx::TxClient,
+        types::{DaSubmissionData, DaSubmissionResult, DataLocation, ProofData, ProofSystem},
+    };
+    use cosmrs::proto::traits::Message;
+
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+
+    fn submission(proof_id: &str) -> DaSubmissionData {
+        DaSubmissionData {
+            proof_id: proof_id.to_string(),
+            proof_data: Some(ProofData {
+                proof: vec![1, 2, 3],
+                vk: vec![4],
+                namespace: "test-namespace".to_string(),
+                data_location: DataLocation::Celestia as i32,
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn in_memory_backend_stores_blobs() {
+        let backend = InMemoryBackend::new(DataLocation::Celestia);
+        let first = DaBlob::from_submission(&submission("a")).unwrap();
+        let second = DaBlob::from_submission(&submission("b")).unwrap();
+        let first_receipt = backend.submit(&first).await.unwrap();
+        let second_receipt = backend.submit(&second).await.unwrap();
+
+        assert_eq!(first_receipt.block_height, 1);
+        assert_eq!(second_receipt.block_height, 2);
+        assert_ne!(first_receipt.block_hash, second_receipt.block_hash);
+        assert_eq!(backend.blobs(), vec![first.clone(), second]);
+        assert_eq!(
+            ProofData::decode(first.data.as_slice()).unwrap(),
+            submission("a").proof_data.unwrap()
+        );
+    }
+
+    #[test]
+    fn blob_requires_proof_data() {
+        let mut submission = submission("a");
+        submission.proof_data = None;
+        assert!(DaBlob::from_submission(&submission).is_err());
+    }
+
+    #[tokio::test]
+    async fn poll_fails_without_node() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let relayer = DaRelayer::new(tx_client, InMemoryBackend::new(DataLocation::Celestia));
+        assert!(relayer.poll_once().await.is_err());
+        assert!(relayer.backend().blobs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn posts_each_proof_once_until_reported() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let relayer = DaRelayer::new(tx_client, InMemoryBackend::new(DataLocation::Celestia));
+        let first = relayer.relay(&submission("a")).await.unwrap();
+        // The report tx failed, so the next poll reuses the posted result.
+        let retried = relayer.relay(&submission("a")).await.unwrap();
+        assert_eq!(first, retried);
+        assert_eq!(relayer.backend().blobs().len(), 1);
+
+        relayer.relay(&submission("b")).await.unwrap();
+        assert_eq!(relayer.backend().blobs().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn posted_store_survives_restart() {
+        let path =
+            std::env::temp_dir().join(format!("fiamma-da-posted-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let relayer = DaRelayer::new(
+            tx_client.clone(),
+            InMemoryBackend::new(DataLocation::Celestia),
+        )
+        .with_posted_store(&path)
+        .unwrap();
+        let posted = relayer.relay(&submission("a")).await.unwrap();
+
+        let restarted = DaRelayer::new(tx_client, InMemoryBackend::new(DataLocation::Celestia))
+            .with_posted_store(&path)
+            .unwrap();
+        assert_eq!(restarted.relay(&submission("a")).await.unwrap(), posted);
+        assert!(restarted.backend().blobs().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn proof_id_matches_chain() {
+        let location = std::env::current_dir().unwrap().join("test-data");
+        let proof_data = ProofData {
+            proof_system: ProofSystem::Groth16Bn254Bitvm as i32,
+            proof: std::fs::read(location.join("proof.bitvm")).unwrap(),
+            public_input: std::fs::read(location.join("public_input.bitvm")).unwrap(),
+            vk: std::fs::read(location.join("vk.bitvm")).unwrap(),
+            namespace: "test-namespace".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            proof_id(&proof_data).unwrap(),
+            "8a17276c37500fe1f0b277f21205592eac037b60f8a7021713ed2b99fe4f78f2"
+        );
+    }
+
+    #[tokio::test]
+    async fn audit_fails_without_node() {
+        let auditor = DaAuditor::from_nodes(
+            NodePool::new(&[UNREACHABLE_NODE]),
+            InMemoryBackend::new(DataLocation::Celestia),
+        )
+        .with_retry_policy(RetryPolicy::none());
+        assert!(auditor.audit("a").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn read_back_and_verify() {
+        let backend = InMemoryBackend::new(DataLocation::Celestia);
+        let submission = submission("placeholder");
+        let proof_data = submission.proof_data.clone().unwrap();
+        let blob = DaBlob {
+            proof_id: proof_id(&proof_data).unwrap(),
+            ..DaBlob::from_submission(&submission).unwrap()
+        };
+        let receipt = backend.submit(&blob).await.unwrap();
+        let result = DaSubmissionResult {
+            proof_id: blob.proof_id.clone(),
+            data_location: DataLocation::Celestia as i32,
+            block_hash: receipt.block_hash,
+            block_height: receipt.block_height,
+            tx_hash: receipt.tx_hash,
+            namespace: blob.namespace.clone(),
+        };
+
+        let data = backend.read(&result).await.unwrap();
+        assert_eq!(verify_blob(&result, &proof_data, &data), Ok(()));
+
+        let mut tampered = proof_data.clone();
+        tampered.proof[0] ^= 1;
+        assert_eq!(
+            verify_blob(&result, &proof_data, &tampered.encode_to_vec()),
+            Err(AvailabilityError::ProofDataMismatch)
+        );
+
+        let wrong_id = DaSubmissionResult {
+            proof_id: "00".repeat(32),
+            ..result.clone()
+        };
+        assert!(matches!(
+            verify_blob(&wrong_id, &proof_data, &data),
+            Err(AvailabilityError::ProofIdMismatch { .. })
+        ));
+
+        let wrong_tx = DaSubmissionResult {
+            tx_hash: "00".repeat(32),
+            ..result
+        };
+        assert!(backend.read(&wrong_tx).await.is_err());
+    }
+}
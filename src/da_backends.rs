@@ -0,0 +1,358 @@
+//! [`DaBackend`]s and [`DaReader`]s for the DA layers named in `DataLocation`. Requires the
+//! `da-backends` feature.
+//!
+//! Celestia and Nubit are reached through the JSON-RPC API of a celestia-node or nubit-node
+//! light node, Avail through the HTTP API of an Avail light client running in app client
+//! mode. The requests follow those nodes' published APIs; the tests here only cover the
+//! encoding and parsing, not live nodes.
+
+use crate::{
+    da::{DaBackend, DaBlob, DaReader, DaReceipt},
+    types::{DaSubmissionResult, DataLocation},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use cosmrs::{ErrorReport, Result};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Size of the user-chosen part of a version 0 Celestia namespace.
+pub const NAMESPACE_ID_SIZE: usize = 10;
+/// `blob.Submit` waits for the blob to be included, so requests get a generous timeout.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+const NAMESPACE_SIZE: usize = 29;
+
+/// Posts blobs through a celestia-node compatible JSON-RPC API, under one version 0
+/// namespace.
+///
+/// `blob.Submit` returns only the inclusion height, so the receipt's `tx_hash` holds the
+/// blob's share commitment, hex encoded, and its `block_hash` the hash of the block at that
+/// height. [`DaReader::read`] fetches the blob back with `blob.Get` by that commitment.
+#[derive(Clone, Debug)]
+pub struct CelestiaBackend {
+    data_location: DataLocation,
+    url: String,
+    auth_token: Option<String>,
+    namespace: [u8; NAMESPACE_SIZE],
+    http: reqwest::Client,
+}
+
+impl CelestiaBackend {
+    /// A backend for `DataLocation::Celestia` posting to the celestia-node at `url`.
+    pub fn new(url: &str, namespace_id: [u8; NAMESPACE_ID_SIZE]) -> Result<Self> {
+        Self::with_data_location(DataLocation::Celestia, url, namespace_id)
+    }
+
+    /// A backend for `DataLocation::Nubit` posting to the nubit-node at `url`, which serves
+    /// the same blob API as celestia-node.
+    pub fn nubit(url: &str, namespace_id: [u8; NAMESPACE_ID_SIZE]) -> Result<Self> {
+        Self::with_data_location(DataLocation::Nubit, url, namespace_id)
+    }
+
+    fn with_data_location(
+        data_location: DataLocation,
+        url: &str,
+        namespace_id: [u8; NAMESPACE_ID_SIZE],
+    ) -> Result<Self> {
+        Ok(Self {
+            data_location,
+            url: url.to_string(),
+            auth_token: None,
+            namespace: namespace(namespace_id),
+            http: http_client()?,
+        })
+    }
+
+    /// The node's RPC auth token, sent as a bearer token.
+    pub fn with_auth_token(mut self, auth_token: &str) -> Self {
+        self.auth_token = Some(auth_token.to_string());
+        self
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let mut request = self.http.post(&self.url).json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }));
+        if let Some(auth_token) = &self.auth_token {
+            request = request.bearer_auth(auth_token);
+        }
+        let mut response: Value = request.send().await?.error_for_status()?.json().await?;
+        if let Some(error) = response.get("error") {
+            return Err(ErrorReport::msg(format!("{} failed: {}", method, error)));
+        }
+        Ok(response["result"].take())
+    }
+}
+
+impl DaBackend for CelestiaBackend {
+    fn data_location(&self) -> DataLocation {
+        self.data_location
+    }
+
+    async fn submit(&self, blob: &DaBlob) -> Result<DaReceipt> {
+        let namespace = BASE64.encode(self.namespace);
+        let data = BASE64.encode(&blob.data);
+        let blob_json = json!({ "namespace": namespace, "data": data, "share_version": 0 });
+        let height = self
+            .call("blob.Submit", json!([[blob_json], {}]))
+            .await?
+            .as_u64()
+            .ok_or(ErrorReport::msg("blob.Submit did not return a height"))?;
+        let blobs = self
+            .call("blob.GetAll", json!([height, [namespace]]))
+            .await?;
+        let commitment = commitment_of(&blobs, &data)?;
+        let header = self.call("header.GetByHeight", json!([height])).await?;
+        Ok(DaReceipt {
+            block_hash: block_hash(&header)?,
+            block_height: height,
+            tx_hash: hex::encode(commitment),
+        })
+    }
+}
+
+impl DaReader for CelestiaBackend {
+    fn data_location(&self) -> DataLocation {
+        self.data_location
+    }
+
+    async fn read(&self, result: &DaSubmissionResult) -> Result<Vec<u8>> {
+        let commitment = hex::decode(&result.tx_hash)?;
+        let blob = self
+            .call(
+                "blob.Get",
+                json!([
+                    result.block_height,
+                    BASE64.encode(self.namespace),
+                    BASE64.encode(commitment)
+                ]),
+            )
+            .await?;
+        let data = blob["data"]
+            .as_str()
+            .ok_or(ErrorReport::msg("blob.Get returned a blob without data"))?;
+        Ok(BASE64.decode(data)?)
+    }
+}
+
+/// Posts blobs through the HTTP API of an Avail light client in app client mode, under the
+/// app ID the client was started with.
+#[derive(Clone, Debug)]
+pub struct AvailBackend {
+    url: String,
+    http: reqwest::Client,
+}
+
+impl AvailBackend {
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            http: http_client()?,
+        })
+    }
+
+    async fn get(&self, path: &str) -> Result<Value> {
+        let url = format!("{}{}", self.url, path);
+        Ok(self
+            .http
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+impl DaBackend for AvailBackend {
+    fn data_location(&self) -> DataLocation {
+        DataLocation::Avail
+    }
+
+    async fn submit(&self, blob: &DaBlob) -> Result<DaReceipt> {
+        let response: Value = self
+            .http
+            .post(format!("{}/v2/submit", self.url))
+            .json(&json!({ "data": BASE64.encode(&blob.data) }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        avail_receipt(&response)
+    }
+}
+
+impl DaReader for AvailBackend {
+    fn data_location(&self) -> DataLocation {
+        DataLocation::Avail
+    }
+
+    async fn read(&self, result: &DaSubmissionResult) -> Result<Vec<u8>> {
+        let path = format!(
+            "/v2/blocks/{}/data?fields=data,extrinsic",
+            result.block_height
+        );
+        avail_data(&self.get(&path).await?, &result.tx_hash)
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .timeout(DEFAULT_REQUEST_TIMEOUT)
+        .build()?)
+}
+
+/// A version 0 namespace: the version byte and 18 zero bytes before `namespace_id`.
+fn namespace(namespace_id: [u8; NAMESPACE_ID_SIZE]) -> [u8; NAMESPACE_SIZE] {
+    let mut namespace = [0; NAMESPACE_SIZE];
+    namespace[NAMESPACE_SIZE - NAMESPACE_ID_SIZE..].copy_from_slice(&namespace_id);
+    namespace
+}
+
+/// The commitment of the blob in `blobs`, a `blob.GetAll` result, whose data is `data`.
+fn commitment_of(blobs: &Value, data: &str) -> Result<Vec<u8>> {
+    let commitment = blobs
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|blob| blob["data"].as_str() == Some(data))
+        .and_then(|blob| blob["commitment"].as_str())
+        .ok_or(ErrorReport::msg("Submitted blob not found at its height"))?;
+    Ok(BASE64.decode(commitment)?)
+}
+
+fn block_hash(header: &Value) -> Result<String> {
+    header["commit"]["block_id"]["hash"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or(ErrorReport::msg("Header without a block hash"))
+}
+
+fn avail_receipt(response: &Value) -> Result<DaReceipt> {
+    let field = |name: &str| {
+        response[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or(ErrorReport::msg(format!(
+                "Avail submit response without {}",
+                name
+            )))
+    };
+    Ok(DaReceipt {
+        block_hash: field("block_hash")?,
+        block_height: response["block_number"].as_u64().ok_or(ErrorReport::msg(
+            "Avail submit response without block_number",
+        ))?,
+        tx_hash: field("hash")?,
+    })
+}
+
+/// The data of the transaction in `block`, a block data response, whose extrinsic hashes to
+/// `tx_hash`. Avail transaction hashes are the BLAKE2b-256 of the encoded extrinsic.
+fn avail_data(block: &Value, tx_hash: &str) -> Result<Vec<u8>> {
+    let tx_hash = tx_hash.trim_start_matches("0x").to_lowercase();
+    for transaction in block["data_transactions"].as_array().into_iter().flatten() {
+        let Some(extrinsic) = transaction["extrinsic"].as_str() else {
+            continue;
+        };
+        let hash = Blake2b::<U32>::digest(BASE64.decode(extrinsic)?);
+        if hex::encode(hash) == tx_hash {
+            let data = transaction["data"]
+                .as_str()
+                .ok_or(ErrorReport::msg("Avail transaction without data"))?;
+            return Ok(BASE64.decode(data)?);
+        }
+    }
+    Err(ErrorReport::msg(format!(
+        "No transaction {} in Avail block {}",
+        tx_hash, block["block_number"]
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        avail_data, avail_receipt, block_hash, commitment_of, namespace, AvailBackend,
+        CelestiaBackend,
+    };
+    use crate::da::{DaBackend, DaBlob};
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+    use blake2::{digest::consts::U32, Blake2b, Digest};
+    use serde_json::json;
+
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+
+    fn blob() -> DaBlob {
+        DaBlob {
+            proof_id: "a".to_string(),
+            namespace: "test-namespace".to_string(),
+            data: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn version_zero_namespace() {
+        let namespace = namespace(*b"fiamma-sdk");
+        assert_eq!(namespace.len(), 29);
+        assert_eq!(namespace[..19], [0; 19]);
+        assert_eq!(&namespace[19..], b"fiamma-sdk");
+    }
+
+    #[test]
+    fn finds_submitted_blob() {
+        let blobs = json!([
+            { "data": BASE64.encode([9]), "commitment": BASE64.encode([1; 32]) },
+            { "data": BASE64.encode([1, 2, 3]), "commitment": BASE64.encode([2; 32]) },
+        ]);
+        assert_eq!(
+            commitment_of(&blobs, &BASE64.encode([1, 2, 3])).unwrap(),
+            vec![2; 32]
+        );
+        assert!(commitment_of(&blobs, &BASE64.encode([4])).is_err());
+        assert!(commitment_of(&json!(null), &BASE64.encode([4])).is_err());
+
+        let header = json!({ "commit": { "block_id": { "hash": "ABCD" } } });
+        assert_eq!(block_hash(&header).unwrap(), "ABCD");
+        assert!(block_hash(&json!({})).is_err());
+    }
+
+    #[test]
+    fn parses_avail_responses() {
+        let receipt = avail_receipt(&json!({
+            "block_number": 12,
+            "block_hash": "0x01",
+            "hash": "0x02",
+            "index": 1,
+        }))
+        .unwrap();
+        assert_eq!(receipt.block_height, 12);
+        assert_eq!(receipt.block_hash, "0x01");
+        assert_eq!(receipt.tx_hash, "0x02");
+        assert!(avail_receipt(&json!({ "block_number": 12 })).is_err());
+
+        let (first, second) = ([1, 1], [2, 2]);
+        let block = json!({
+            "block_number": 12,
+            "data_transactions": [
+                { "data": BASE64.encode([7]), "extrinsic": BASE64.encode(first) },
+                { "data": BASE64.encode([8]), "extrinsic": BASE64.encode(second) },
+            ],
+        });
+        let tx_hash = format!("0x{}", hex::encode(Blake2b::<U32>::digest(second)));
+        assert_eq!(avail_data(&block, &tx_hash).unwrap(), vec![8]);
+        assert!(avail_data(&block, "0x00").is_err());
+    }
+
+    #[tokio::test]
+    async fn submit_fails_without_node() {
+        let celestia = CelestiaBackend::new(UNREACHABLE_NODE, *b"fiamma-sdk").unwrap();
+        assert!(celestia.submit(&blob()).await.is_err());
+        let avail = AvailBackend::new(UNREACHABLE_NODE).unwrap();
+        assert!(avail.submit(&blob()).await.is_err());
+    }
+}
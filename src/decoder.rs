@@ -0,0 +1,336 @@
+//! Decoding of signed transactions into typed Fiamma and cosmos messages.
+
+use crate::{
+    authz::{MsgExec, MsgGrant, MsgRevoke, MSG_REVOKE_TYPE_URL},
+    chain::ACCOUNT_PREFIX,
+    multisig::MultisigAccount,
+    types::{
+        BitvmstakerMsgUpdateParams, MsgCreateStaker, MsgRegisterVK, MsgRemoveStaker, MsgRemoveVK,
+        MsgSubmitCommunityVerification, MsgSubmitProof, MsgUpdateCommitteeAddress,
+        MsgUpdateDASubmissionResults, MsgUpdateDASubmitter, ZkpverifyMsgUpdateParams,
+    },
+};
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use cosmrs::{
+    bank::{MsgMultiSend, MsgSend},
+    distribution::MsgWithdrawDelegatorReward,
+    feegrant::{MsgGrantAllowance, MsgRevokeAllowance},
+    proto::{
+        tendermint::v0_34::abci::Event,
+        traits::{Message, Name},
+    },
+    staking::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate},
+    tendermint::Block,
+    tx::{AuthInfo, Body, Fee, Msg, SignerPublicKey, Tx},
+    AccountId, Any, ErrorReport, Result,
+};
+use sha2::{Digest, Sha256};
+
+/// A message of a Fiamma transaction. Message types the SDK does not know are kept as
+/// [`FiammaMsg::Other`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FiammaMsg {
+    SubmitProof(MsgSubmitProof),
+    SubmitCommunityVerification(MsgSubmitCommunityVerification),
+    UpdateDASubmissionResults(MsgUpdateDASubmissionResults),
+    UpdateDASubmitter(MsgUpdateDASubmitter),
+    UpdateZkpverifyParams(ZkpverifyMsgUpdateParams),
+    CreateStaker(MsgCreateStaker),
+    RemoveStaker(MsgRemoveStaker),
+    UpdateCommitteeAddress(MsgUpdateCommitteeAddress),
+    RegisterVK(MsgRegisterVK),
+    RemoveVK(MsgRemoveVK),
+    UpdateBitvmstakerParams(BitvmstakerMsgUpdateParams),
+    Send(MsgSend),
+    MultiSend(MsgMultiSend),
+    Delegate(MsgDelegate),
+    Undelegate(MsgUndelegate),
+    BeginRedelegate(MsgBeginRedelegate),
+    WithdrawDelegatorReward(MsgWithdrawDelegatorReward),
+    Grant(MsgGrant),
+    Revoke(MsgRevoke),
+    Exec(MsgExec),
+    GrantAllowance(MsgGrantAllowance),
+    RevokeAllowance(MsgRevokeAllowance),
+    Other(Any),
+}
+
+impl FiammaMsg {
+    /// Decodes `any` by its type URL. Fails only if a known type URL carries an invalid
+    /// message.
+    pub fn from_any(any: &Any) -> Result<Self> {
+        decode(any, FiammaMsg::SubmitProof)
+            .or_else(|| decode(any, FiammaMsg::SubmitCommunityVerification))
+            .or_else(|| decode(any, FiammaMsg::UpdateDASubmissionResults))
+            .or_else(|| decode(any, FiammaMsg::UpdateDASubmitter))
+            .or_else(|| decode_proto(any, FiammaMsg::UpdateZkpverifyParams))
+            .or_else(|| decode(any, FiammaMsg::CreateStaker))
+            .or_else(|| decode(any, FiammaMsg::RemoveStaker))
+            .or_else(|| decode(any, FiammaMsg::UpdateCommitteeAddress))
+            .or_else(|| decode(any, FiammaMsg::RegisterVK))
+            .or_else(|| decode(any, FiammaMsg::RemoveVK))
+            .or_else(|| decode_proto(any, FiammaMsg::UpdateBitvmstakerParams))
+            .or_else(|| decode(any, FiammaMsg::Send))
+            .or_else(|| decode(any, FiammaMsg::MultiSend))
+            .or_else(|| decode(any, FiammaMsg::Delegate))
+            .or_else(|| decode(any, FiammaMsg::Undelegate))
+            .or_else(|| decode(any, FiammaMsg::BeginRedelegate))
+            .or_else(|| decode(any, FiammaMsg::WithdrawDelegatorReward))
+            .or_else(|| decode(any, FiammaMsg::Grant))
+            .or_else(|| {
+                (any.type_url == MSG_REVOKE_TYPE_URL)
+                    .then(|| MsgRevoke::from_any(any).map(FiammaMsg::Revoke))
+            })
+            .or_else(|| decode(any, FiammaMsg::Exec))
+            .or_else(|| decode(any, FiammaMsg::GrantAllowance))
+            .or_else(|| decode(any, FiammaMsg::RevokeAllowance))
+            .unwrap_or_else(|| Ok(FiammaMsg::Other(any.clone())))
+    }
+
+    /// The messages wrapped in a `MsgExec`, decoded. Empty for every other message.
+    pub fn exec_msgs(&self) -> Result<Vec<FiammaMsg>> {
+        match self {
+            FiammaMsg::Exec(exec) => exec.msgs.iter().map(FiammaMsg::from_any).collect(),
+            _ => Ok(vec![]),
+        }
+    }
+}
+
+fn decode<M: Msg>(any: &Any, variant: fn(M) -> FiammaMsg) -> Option<Result<FiammaMsg>> {
+    (any.type_url == M::Proto::type_url()).then(|| M::from_any(any).map(variant))
+}
+
+fn decode_proto<M: Message + Name + Default>(
+    any: &Any,
+    variant: fn(M) -> FiammaMsg,
+) -> Option<Result<FiammaMsg>> {
+    (any.type_url == M::type_url()).then(|| Ok(variant(any.to_msg()?)))
+}
+
+/// The execution result of a transaction, as returned with `GetTx` and `GetTxsEvent`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TxResult {
+    pub height: u64,
+    /// Zero on success.
+    pub code: u32,
+    pub codespace: String,
+    pub raw_log: String,
+    pub gas_wanted: i64,
+    pub gas_used: i64,
+    pub events: Vec<Event>,
+}
+
+impl TxResult {
+    pub fn is_success(&self) -> bool {
+        self.code == 0
+    }
+
+    /// Values of `attribute` on events of type `event_type`, in emission order.
+    pub fn event_attributes(&self, event_type: &str, attribute: &str) -> Vec<String> {
+        self.events
+            .iter()
+            .filter(|event| event.r#type == event_type)
+            .flat_map(|event| &event.attributes)
+            .filter(|attr| attr.key == attribute.as_bytes())
+            .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+            .collect()
+    }
+}
+
+/// A signed transaction with its messages decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedTx {
+    /// Upper case hex, as used by `GetTx`.
+    pub hash: String,
+    pub body: Body,
+    pub auth_info: AuthInfo,
+    pub signatures: Vec<Vec<u8>>,
+    /// The decoded messages, in order. A message of a known type that fails to decode is
+    /// kept as [`FiammaMsg::Other`] so the rest of the transaction stays readable.
+    pub msgs: Vec<FiammaMsg>,
+    /// The accounts of the signer infos, in order. Signer infos without a public key, which
+    /// the SDK allows once the account's key is stored on chain, and those with a key type
+    /// other than secp256k1 or legacy amino multisig are skipped, so this can be shorter
+    /// than `auth_info.signer_infos`.
+    pub signers: Vec<AccountId>,
+    /// `None` for transactions decoded from raw bytes.
+    pub result: Option<TxResult>,
+}
+
+impl DecodedTx {
+    /// Decodes raw transaction bytes as found in blocks and broadcast requests.
+    pub fn from_bytes(tx_bytes: &[u8]) -> Result<Self> {
+        let hash = hex::encode_upper(Sha256::digest(tx_bytes));
+        Self::new(hash, Tx::from_bytes(tx_bytes)?, None)
+    }
+
+    /// Decodes the transaction of a `GetTx` or `GetTxsEvent` response.
+    pub fn from_tx_response(tx_response: &TxResponse) -> Result<Self> {
+        let tx = tx_response
+            .tx
+            .as_ref()
+            .ok_or(ErrorReport::msg("Empty tx in TxResponse"))?;
+        let result = TxResult {
+            height: u64::try_from(tx_response.height)?,
+            code: tx_response.code,
+            codespace: tx_response.codespace.clone(),
+            raw_log: tx_response.raw_log.clone(),
+            gas_wanted: tx_response.gas_wanted,
+            gas_used: tx_response.gas_used,
+            events: tx_response.events.clone(),
+        };
+        Self::new(
+            tx_response.txhash.clone(),
+            Tx::from_bytes(&tx.value)?,
+            Some(result),
+        )
+    }
+
+    fn new(hash: String, tx: Tx, result: Option<TxResult>) -> Result<Self> {
+        let msgs = tx
+            .body
+            .messages
+            .iter()
+            .map(|any| FiammaMsg::from_any(any).unwrap_or_else(|_| FiammaMsg::Other(any.clone())))
+            .collect();
+        let signers = tx
+            .auth_info
+            .signer_infos
+            .iter()
+            .filter_map(|signer_info| match signer_info.public_key.as_ref()? {
+                SignerPublicKey::Single(public_key) => Some(public_key.account_id(ACCOUNT_PREFIX)),
+                SignerPublicKey::LegacyAminoMultisig(multisig) => Some(
+                    MultisigAccount::new(multisig.threshold, multisig.public_keys.clone())
+                        .map(|account| account.account_id),
+                ),
+                SignerPublicKey::Any(_) => None,
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            hash,
+            body: tx.body,
+            auth_info: tx.auth_info,
+            signatures: tx.signatures,
+            msgs,
+            signers,
+            result,
+        })
+    }
+
+    pub fn fee(&self) -> &Fee {
+        &self.auth_info.fee
+    }
+
+    pub fn memo(&self) -> &str {
+        &self.body.memo
+    }
+}
+
+/// Decodes every transaction in `block`.
+pub fn decode_block(block: &Block) -> Result<Vec<DecodedTx>> {
+    decode_txs(&block.data)
+}
+
+pub fn decode_txs(txs: &[Vec<u8>]) -> Result<Vec<DecodedTx>> {
+    txs.iter().map(|tx| DecodedTx::from_bytes(tx)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_txs, DecodedTx, FiammaMsg};
+    use crate::{authz::MsgExec, chain::DENOM, tx::TxClient, types::MsgRegisterVK, wallet::Wallet};
+    use cosmrs::{bank::MsgSend, tx::Msg, Any, Coin};
+
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const RECEIVER_PRIVATE_KEY: &str =
+        "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897";
+
+    #[tokio::test]
+    async fn decode_signed_tx() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let register_vk = MsgRegisterVK {
+            creator: tx_client.account_id(),
+            vk: vec![1, 2, 3],
+        };
+        let send = MsgSend {
+            from_address: tx_client.account_id(),
+            to_address: Wallet::new(RECEIVER_PRIVATE_KEY).account_id,
+            amount: vec![Coin {
+                amount: 10,
+                denom: DENOM.parse().unwrap(),
+            }],
+        };
+        let unknown = Any {
+            type_url: "/example.v1.MsgUnknown".to_string(),
+            value: vec![],
+        };
+        let exec = MsgExec {
+            grantee: tx_client.account_id(),
+            msgs: vec![register_vk.to_any().unwrap()],
+        };
+        let raw = tx_client
+            .sign_tx(
+                vec![
+                    register_vk.to_any().unwrap(),
+                    send.to_any().unwrap(),
+                    unknown.clone(),
+                    exec.to_any().unwrap(),
+                ],
+                0,
+                0,
+            )
+            .unwrap();
+
+        let tx_bytes = raw.to_bytes().unwrap();
+        let tx = DecodedTx::from_bytes(&tx_bytes).unwrap();
+        assert_eq!(tx.hash.len(), 64);
+        assert_eq!(
+            tx.msgs[..3],
+            [
+                FiammaMsg::RegisterVK(register_vk.clone()),
+                FiammaMsg::Send(send),
+                FiammaMsg::Other(unknown),
+            ]
+        );
+        assert_eq!(
+            tx.msgs[3].exec_msgs().unwrap(),
+            vec![FiammaMsg::RegisterVK(register_vk)]
+        );
+        assert_eq!(tx.signers, vec![tx_client.account_id()]);
+        assert_eq!(tx.fee().amount[0].amount, 2000);
+        assert_eq!(decode_txs(&[tx_bytes]).unwrap(), vec![tx]);
+    }
+
+    #[test]
+    fn reject_invalid_known_msg() {
+        let invalid = Any {
+            type_url: "/fiamma.bitvmstaker.MsgRegisterVK".to_string(),
+            value: vec![0x0a, 0x03, b'b', b'a', b'd'],
+        };
+        assert!(FiammaMsg::from_any(&invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn keep_invalid_known_msg_as_other() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let invalid = Any {
+            type_url: "/fiamma.bitvmstaker.MsgRegisterVK".to_string(),
+            value: vec![0x0a, 0x03, b'b', b'a', b'd'],
+        };
+        let send = MsgSend {
+            from_address: tx_client.account_id(),
+            to_address: tx_client.account_id(),
+            amount: vec![],
+        };
+        let raw = tx_client
+            .sign_tx(vec![invalid.clone(), send.to_any().unwrap()], 0, 0)
+            .unwrap();
+
+        let tx = DecodedTx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            tx.msgs,
+            vec![FiammaMsg::Other(invalid), FiammaMsg::Send(send)]
+        );
+    }
+}
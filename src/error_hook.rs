@@ -0,0 +1,31 @@
+use cosmrs::ErrorReport;
+use std::{fmt, sync::Arc};
+
+type Hook = Arc<dyn Fn(&ErrorReport) + Send + Sync>;
+
+/// Receives errors and warnings that the SDK recovers from instead of returning, such as a
+/// failed poll in a long-running loop. The default hook drops them.
+#[derive(Clone, Default)]
+pub struct ErrorHook(Option<Hook>);
+
+impl ErrorHook {
+    pub fn new(hook: impl Fn(&ErrorReport) + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(hook)))
+    }
+
+    pub fn report(&self, error: &ErrorReport) {
+        if let Some(hook) = &self.0 {
+            hook(error);
+        }
+    }
+}
+
+impl fmt::Debug for ErrorHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "ErrorHook(set)"
+        } else {
+            "ErrorHook(none)"
+        })
+    }
+}
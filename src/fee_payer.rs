@@ -0,0 +1,222 @@
+use crate::{
+    chain::ACCOUNT_PREFIX,
+    multisig::{verify_signature, PartialSignature},
+    wallet::Wallet,
+};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use cosmrs::{
+    crypto::PublicKey,
+    tendermint::chain::Id as ChainId,
+    tx::{AccountNumber, AuthInfo, Body, Fee, Raw, SequenceNumber, SignDoc, SignerInfo},
+    AccountId, ErrorReport, Result,
+};
+
+/// A signer of a [`FeePayerTx`]: its public key, account number and sequence.
+#[derive(Debug, Clone)]
+pub struct TxSigner {
+    pub public_key: PublicKey,
+    pub account_number: AccountNumber,
+    pub sequence: SequenceNumber,
+}
+
+/// A transaction whose fee is paid by another account, which must co-sign it.
+///
+/// The chain orders signers as the messages' signers followed by the fee payer, so the
+/// submitter signs first. Both sign the same body and auth info with `SIGN_MODE_DIRECT`,
+/// each with its own account number.
+#[derive(Debug, Clone)]
+pub struct FeePayerTx {
+    pub body_bytes: Vec<u8>,
+    pub auth_info_bytes: Vec<u8>,
+    pub chain_id: ChainId,
+    signers: [TxSigner; 2],
+}
+
+impl FeePayerTx {
+    /// Builds the transaction of `submitter`, with `fee` paid by `payer`.
+    pub fn new(
+        body: &Body,
+        fee: Fee,
+        chain_id: &ChainId,
+        submitter: TxSigner,
+        payer: TxSigner,
+    ) -> Result<Self> {
+        let payer_id = payer.public_key.account_id(ACCOUNT_PREFIX)?;
+        if submitter.public_key == payer.public_key {
+            return Err(ErrorReport::msg(
+                "Fee payer is the submitter, no co-signature is needed",
+            ));
+        }
+        let mut fee = fee;
+        fee.payer = Some(payer_id);
+        let auth_info = AuthInfo {
+            signer_infos: [&submitter, &payer]
+                .iter()
+                .map(|signer| SignerInfo::single_direct(Some(signer.public_key), signer.sequence))
+                .collect(),
+            fee,
+        };
+        Ok(Self {
+            body_bytes: body.clone().into_bytes()?,
+            auth_info_bytes: auth_info.into_bytes()?,
+            chain_id: chain_id.clone(),
+            signers: [submitter, payer],
+        })
+    }
+
+    pub fn payer(&self) -> Result<AccountId> {
+        self.signers[1].public_key.account_id(ACCOUNT_PREFIX)
+    }
+
+    /// The sign doc `public_key` signs: the shared body and auth info with its account number.
+    pub fn sign_doc(&self, public_key: &PublicKey) -> Result<SignDoc> {
+        let signer = &self.signers[self.signer_position(public_key)?];
+        Ok(SignDoc {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: self.auth_info_bytes.clone(),
+            chain_id: self.chain_id.to_string(),
+            account_number: signer.account_number,
+        })
+    }
+
+    pub fn sign(&self, wallet: &Wallet) -> Result<PartialSignature> {
+        let sign_bytes = self.sign_doc(&wallet.public_key)?.into_bytes()?;
+        Ok(PartialSignature {
+            public_key: wallet.public_key,
+            signature: wallet.sign_bytes(&sign_bytes)?,
+        })
+    }
+
+    /// Checks each signature against its signer and assembles the transaction.
+    pub fn aggregate(self, signatures: Vec<PartialSignature>) -> Result<Raw> {
+        let mut ordered: [Option<Vec<u8>>; 2] = [None, None];
+        for partial in signatures {
+            let sign_bytes = self.sign_doc(&partial.public_key)?.into_bytes()?;
+            verify_signature(&partial.public_key, &sign_bytes, &partial.signature)?;
+            ordered[self.signer_position(&partial.public_key)?] = Some(partial.signature);
+        }
+        let signatures =
+            ordered
+                .into_iter()
+                .collect::<Option<Vec<_>>>()
+                .ok_or(ErrorReport::msg(
+                    "Fee payer transactions need both the submitter's and the payer's signature",
+                ))?;
+        Ok(TxRaw {
+            body_bytes: self.body_bytes,
+            auth_info_bytes: self.auth_info_bytes,
+            signatures,
+        }
+        .into())
+    }
+
+    fn signer_position(&self, public_key: &PublicKey) -> Result<usize> {
+        self.signers
+            .iter()
+            .position(|signer| signer.public_key == *public_key)
+            .ok_or(ErrorReport::msg(format!(
+                "Public key {} is not a signer of this transaction",
+                public_key.to_json()
+            )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeePayerTx, TxSigner};
+    use crate::{chain::*, types::MsgSubmitCommunityVerification, wallet::Wallet};
+    use cosmrs::{
+        tx::{BodyBuilder, Fee, Msg, Tx},
+        Coin,
+    };
+
+    const SUBMITTER_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const PAYER_KEY: &str = "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897";
+
+    fn fee_payer_tx(submitter: &Wallet, payer: &Wallet) -> FeePayerTx {
+        let msg = MsgSubmitCommunityVerification {
+            creator: submitter.account_id.clone(),
+            proof_id: "proof".to_string(),
+            verify_result: true,
+        };
+        let body = BodyBuilder::new().msg(msg.to_any().unwrap()).finish();
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                amount: 2000,
+                denom: DENOM.parse().unwrap(),
+            },
+            200_000_u64,
+        );
+        let signer = |wallet: &Wallet, account_number| TxSigner {
+            public_key: wallet.public_key,
+            account_number,
+            sequence: 3,
+        };
+        FeePayerTx::new(
+            &body,
+            fee,
+            &CHAIN_ID.parse().unwrap(),
+            signer(submitter, 10),
+            signer(payer, 20),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn co_signed_by_submitter_and_payer() {
+        let (submitter, payer) = (Wallet::new(SUBMITTER_KEY), Wallet::new(PAYER_KEY));
+        let tx = fee_payer_tx(&submitter, &payer);
+        assert_eq!(tx.payer().unwrap(), payer.account_id);
+        assert_eq!(tx.sign_doc(&payer.public_key).unwrap().account_number, 20);
+
+        let signatures = vec![tx.sign(&payer).unwrap(), tx.sign(&submitter).unwrap()];
+        let raw = tx.clone().aggregate(signatures.clone()).unwrap();
+        let decoded = Tx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.auth_info.fee.payer, Some(payer.account_id.clone()));
+        let signer_keys: Vec<_> = decoded
+            .auth_info
+            .signer_infos
+            .iter()
+            .map(|info| info.public_key.clone())
+            .collect();
+        assert_eq!(
+            signer_keys,
+            vec![
+                Some(submitter.public_key.into()),
+                Some(payer.public_key.into())
+            ]
+        );
+        assert_eq!(decoded.signatures[0], signatures[1].signature);
+
+        assert!(tx.clone().aggregate(vec![signatures[0].clone()]).is_err());
+        let mut forged = signatures[0].clone();
+        forged.public_key = submitter.public_key;
+        assert!(tx.aggregate(vec![forged, signatures[1].clone()]).is_err());
+    }
+
+    #[test]
+    fn rejects_submitter_as_payer() {
+        let submitter = Wallet::new(SUBMITTER_KEY);
+        let body = BodyBuilder::new().finish();
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                amount: 2000,
+                denom: DENOM.parse().unwrap(),
+            },
+            200_000_u64,
+        );
+        let signer = TxSigner {
+            public_key: submitter.public_key,
+            account_number: 1,
+            sequence: 0,
+        };
+        assert!(FeePayerTx::new(
+            &body,
+            fee,
+            &CHAIN_ID.parse().unwrap(),
+            signer.clone(),
+            signer
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,125 @@
+#![allow(dead_code)]
+
+use crate::{
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
+};
+use cosmos_sdk_proto::cosmos::feegrant::v1beta1::{
+    query_client::QueryClient as ProtoQueryClient, Grant, QueryAllowanceRequest,
+    QueryAllowancesByGranterRequest, QueryAllowancesRequest,
+};
+
+use cosmrs::{ErrorReport, Result};
+
+#[derive(Debug, Clone)]
+pub struct QueryClient {
+    nodes: NodePool,
+    retry_policy: RetryPolicy,
+}
+
+impl QueryClient {
+    pub fn new(rpc: &str) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]))
+    }
+
+    /// A client that routes queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool) -> Self {
+        Self {
+            nodes,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.nodes.channel(&self.retry_policy)
+    }
+
+    pub async fn get_allowance(&self, granter: &str, grantee: &str) -> Result<Grant> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let resp = client
+            .allowance(QueryAllowanceRequest {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+            })
+            .await?;
+        let allowance = resp
+            .get_ref()
+            .clone()
+            .allowance
+            .ok_or(ErrorReport::msg("Empty allowance in response"))?;
+        Ok(allowance)
+    }
+
+    pub async fn get_allowances(&self, grantee: &str) -> Result<Vec<Grant>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let resp = client
+            .allowances(QueryAllowancesRequest {
+                grantee: grantee.to_string(),
+                pagination: None,
+            })
+            .await?;
+        Ok(resp.get_ref().clone().allowances)
+    }
+
+    pub async fn get_allowances_by_granter(&self, granter: &str) -> Result<Vec<Grant>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let resp = client
+            .allowances_by_granter(QueryAllowancesByGranterRequest {
+                granter: granter.to_string(),
+                pagination: None,
+            })
+            .await?;
+        Ok(resp.get_ref().clone().allowances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryClient;
+    use crate::wallet::Wallet;
+    const NODE: &str = "http://54.65.75.57:9090";
+    const GRANTER_PRIVATE_KEY: &str =
+        "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const GRANTEE_PRIVATE_KEY: &str =
+        "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897";
+
+    fn granter() -> String {
+        Wallet::new(GRANTER_PRIVATE_KEY).account_id.to_string()
+    }
+
+    fn grantee() -> String {
+        Wallet::new(GRANTEE_PRIVATE_KEY).account_id.to_string()
+    }
+
+    #[tokio::test]
+    async fn test_get_allowance() {
+        let client = QueryClient::new(NODE);
+        let allowance = client.get_allowance(&granter(), &grantee()).await;
+        println!("allowance: {:?}", allowance);
+    }
+
+    #[tokio::test]
+    async fn test_get_allowances() {
+        let client = QueryClient::new(NODE);
+        let allowances = client.get_allowances(&grantee()).await;
+        println!("allowances: {:?}", allowances);
+    }
+
+    #[tokio::test]
+    async fn test_get_allowances_by_granter() {
+        let client = QueryClient::new(NODE);
+        let allowances = client.get_allowances_by_granter(&granter()).await;
+        println!("allowances_by_granter: {:?}", allowances);
+    }
+}
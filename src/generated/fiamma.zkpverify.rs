@@ -1,15 +1,18 @@
 // This file is @generated by prost-build.
 /// Params defines the parameters for the module.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Params {}
 /// DASubmissionResult is the data structure for the DA submission result
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DaSubmissionResult {
     #[prost(string, tag = "1")]
     pub proof_id: ::prost::alloc::string::String,
     #[prost(enumeration = "DataLocation", tag = "2")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::data_location"))]
     pub data_location: i32,
     #[prost(string, tag = "3")]
     pub block_hash: ::prost::alloc::string::String,
@@ -21,6 +24,7 @@ pub struct DaSubmissionResult {
     pub namespace: ::prost::alloc::string::String,
 }
 /// DASubmissionData is the data structure for the DA submission data
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DaSubmissionData {
@@ -30,33 +34,45 @@ pub struct DaSubmissionData {
     pub proof_data: ::core::option::Option<ProofData>,
 }
 /// ProofData is the data structure for the proof verification request
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProofData {
     #[prost(enumeration = "ProofSystem", tag = "1")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::proof_system"))]
     pub proof_system: i32,
     #[prost(bytes = "vec", tag = "2")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub proof: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "3")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub public_input: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "4")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub vk: ::prost::alloc::vec::Vec<u8>,
     #[prost(enumeration = "DataLocation", tag = "5")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::data_location"))]
     pub data_location: i32,
     #[prost(string, tag = "6")]
     pub namespace: ::prost::alloc::string::String,
 }
 /// VerifyResult is the data structure for the proof verification result
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct VerifyResult {
     #[prost(string, tag = "1")]
     pub proof_id: ::prost::alloc::string::String,
     #[prost(enumeration = "ProofSystem", tag = "2")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::proof_system"))]
     pub proof_system: i32,
     #[prost(bool, tag = "3")]
     pub result: bool,
     #[prost(enumeration = "VerificationStatus", tag = "4")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::verification_status")
+    )]
     pub status: i32,
     #[prost(uint64, tag = "5")]
     pub community_verification_count: u64,
@@ -64,12 +80,15 @@ pub struct VerifyResult {
     pub namespace: ::prost::alloc::string::String,
 }
 /// BitVMChallengeData is the data structure for the bitvm challenge data
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BitVmChallengeData {
     #[prost(bytes = "vec", tag = "1")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub witness: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "2")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub vk: ::prost::alloc::vec::Vec<u8>,
     #[prost(string, tag = "3")]
     pub proposer: ::prost::alloc::string::String,
@@ -165,6 +184,7 @@ impl ProofSystem {
     }
 }
 /// MsgUpdateParams is the Msg/UpdateParams request type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MsgUpdateParams {
@@ -42,6 +42,24 @@ impl_name!(
     "MsgSubmitCommunityVerification"
 );
 
+impl_name!(
+    fiamma::zkpverify::MsgUpdateDaSubmissionResults,
+    "fiamma.zkpverify",
+    "MsgUpdateDASubmissionResults"
+);
+
+impl_name!(
+    fiamma::zkpverify::MsgUpdateDaSubmitter,
+    "fiamma.zkpverify",
+    "MsgUpdateDASubmitter"
+);
+
+impl_name!(
+    fiamma::zkpverify::MsgUpdateParams,
+    "fiamma.zkpverify",
+    "MsgUpdateParams"
+);
+
 impl_name!(
     fiamma::bitvmstaker::MsgCreateStaker,
     "fiamma.bitvmstaker",
@@ -54,6 +72,18 @@ impl_name!(
     "MsgRemoveStaker"
 );
 
+impl_name!(
+    fiamma::bitvmstaker::MsgUpdateCommitteeAddress,
+    "fiamma.bitvmstaker",
+    "MsgUpdateCommitteeAddress"
+);
+
+impl_name!(
+    fiamma::bitvmstaker::MsgUpdateParams,
+    "fiamma.bitvmstaker",
+    "MsgUpdateParams"
+);
+
 impl_name!(
     fiamma::bitvmstaker::MsgRegisterVk,
     "fiamma.bitvmstaker",
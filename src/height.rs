@@ -0,0 +1,110 @@
+use tonic::{metadata::MetadataValue, Request, Response};
+
+/// gRPC metadata key the Cosmos SDK reads the query height from and reports the served
+/// height in.
+pub const BLOCK_HEIGHT_METADATA: &str = "x-cosmos-block-height";
+
+/// A query result with the block height the node reported serving it at, or `None` if the
+/// node did not report one. Pages after the first of a paginated result are requested at
+/// the height the first page was served at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtHeight<T> {
+    pub value: T,
+    pub height: Option<u64>,
+}
+
+impl<T> AtHeight<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> AtHeight<U> {
+        AtHeight {
+            value: f(self.value),
+            height: self.height,
+        }
+    }
+
+    pub(crate) fn from_response<R>(response: &Response<R>, value: T) -> Self {
+        Self {
+            value,
+            height: served_height(response),
+        }
+    }
+}
+
+/// The block height queries are made at, or `None` for the latest block.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct QueryHeight(Option<u64>);
+
+impl QueryHeight {
+    pub(crate) fn at(height: u64) -> Self {
+        Self(Some(height))
+    }
+
+    pub(crate) fn height(&self) -> Option<u64> {
+        self.0
+    }
+
+    /// The height to request the pages after the first of a paginated query at: `served`,
+    /// the height the first page was served at, so every page reads the same state even if
+    /// the chain advances or another node answers. This height if the node reported none.
+    pub(crate) fn for_pages(&self, served: Option<u64>) -> QueryHeight {
+        served.map_or(*self, QueryHeight::at)
+    }
+
+    pub(crate) fn request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        if let Some(height) = self.0 {
+            request
+                .metadata_mut()
+                .insert(BLOCK_HEIGHT_METADATA, MetadataValue::from(height));
+        }
+        request
+    }
+}
+
+/// The block height the node reported serving `response` at.
+pub(crate) fn served_height<T>(response: &Response<T>) -> Option<u64> {
+    response
+        .metadata()
+        .get(BLOCK_HEIGHT_METADATA)
+        .and_then(|height| height.to_str().ok())
+        .and_then(|height| height.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AtHeight, QueryHeight, BLOCK_HEIGHT_METADATA};
+    use tonic::{metadata::MetadataValue, Response};
+
+    #[test]
+    fn sets_and_reads_height() {
+        let latest = QueryHeight::default();
+        assert!(latest
+            .request(())
+            .metadata()
+            .get(BLOCK_HEIGHT_METADATA)
+            .is_none());
+
+        let at_height = QueryHeight::at(1234);
+        let request = at_height.request(());
+        assert_eq!(
+            request.metadata().get(BLOCK_HEIGHT_METADATA).unwrap(),
+            "1234"
+        );
+
+        assert_eq!(latest.for_pages(None).height(), None);
+        assert_eq!(latest.for_pages(Some(1234)).height(), Some(1234));
+
+        let mut response = Response::new(());
+        assert_eq!(AtHeight::from_response(&response, "a").height, None);
+        response
+            .metadata_mut()
+            .insert(BLOCK_HEIGHT_METADATA, MetadataValue::from(1234_u64));
+        let served = AtHeight::from_response(&response, "a").map(str::len);
+        assert_eq!(
+            served,
+            AtHeight {
+                value: 1,
+                height: Some(1234)
+            }
+        );
+    }
+}
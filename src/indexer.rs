@@ -0,0 +1,529 @@
+//! Indexes zkpverify activity into a local store.
+//!
+//! CometBFT blocks are final once committed, so the indexer follows the latest height
+//! directly and never has to roll back. Each block's records are written together with the
+//! checkpoint, so a restarted indexer resumes at the first block it had not finished.
+
+use crate::{
+    chain_query::ChainClient,
+    decoder::{DecodedTx, FiammaMsg},
+    error_hook::ErrorHook,
+    node_pool::NodePool,
+    retry::RetryPolicy,
+    tx_query::{QueryClient as TxQueryClient, TxSearch},
+    types::DataLocation,
+};
+use cosmrs::{ErrorReport, Result};
+use std::{collections::BTreeMap, future::Future, time::Duration, time::SystemTime};
+
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Blocks indexed per poll, so a long catch-up still checkpoints regularly.
+pub const DEFAULT_BATCH_SIZE: u64 = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofRecord {
+    pub proof_id: String,
+    pub height: u64,
+    pub tx_hash: String,
+    pub creator: String,
+    pub namespace: String,
+    pub proof_system: String,
+    pub data_location: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
+    pub proof: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
+    pub public_input: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
+    pub vk: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerificationRecord {
+    pub proof_id: String,
+    pub height: u64,
+    pub tx_hash: String,
+    pub verifier: String,
+    pub verify_result: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DaSubmissionRecord {
+    pub proof_id: String,
+    pub height: u64,
+    pub tx_hash: String,
+    pub submitter: String,
+    pub namespace: String,
+    pub data_location: String,
+    pub da_block_hash: String,
+    pub da_block_height: u64,
+    pub da_tx_hash: String,
+}
+
+/// The zkpverify records of one block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedBlock {
+    pub height: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::option_rfc3339")
+    )]
+    pub time: Option<SystemTime>,
+    pub proofs: Vec<ProofRecord>,
+    pub verifications: Vec<VerificationRecord>,
+    pub da_submissions: Vec<DaSubmissionRecord>,
+}
+
+impl IndexedBlock {
+    /// Extracts the records of the successful transactions in `txs`, including messages
+    /// executed through authz `MsgExec`.
+    pub fn from_txs(height: u64, time: Option<SystemTime>, txs: &[DecodedTx]) -> Result<Self> {
+        let mut block = IndexedBlock {
+            height,
+            time,
+            ..Default::default()
+        };
+        for tx in txs {
+            if tx
+                .result
+                .as_ref()
+                .is_some_and(|result| !result.is_success())
+            {
+                continue;
+            }
+            block.add_all(&tx.hash, tx.msgs.clone())?;
+        }
+        Ok(block)
+    }
+
+    fn add_all(&mut self, tx_hash: &str, msgs: Vec<FiammaMsg>) -> Result<()> {
+        for msg in msgs {
+            let exec_msgs = msg.exec_msgs()?;
+            self.add(tx_hash, msg);
+            self.add_all(tx_hash, exec_msgs)?;
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, tx_hash: &str, msg: FiammaMsg) {
+        match msg {
+            FiammaMsg::SubmitProof(msg) => self.proofs.push(ProofRecord {
+                proof_id: msg.proof_id(),
+                height: self.height,
+                tx_hash: tx_hash.to_string(),
+                creator: msg.creator.to_string(),
+                namespace: msg.namespace,
+                proof_system: msg.proof_system,
+                data_location: msg.data_location,
+                proof: msg.proof,
+                public_input: msg.public_input,
+                vk: msg.vk,
+            }),
+            FiammaMsg::SubmitCommunityVerification(msg) => {
+                self.verifications.push(VerificationRecord {
+                    proof_id: msg.proof_id,
+                    height: self.height,
+                    tx_hash: tx_hash.to_string(),
+                    verifier: msg.creator.to_string(),
+                    verify_result: msg.verify_result,
+                })
+            }
+            FiammaMsg::UpdateDASubmissionResults(msg) => {
+                for result in msg.da_submission_results {
+                    let data_location = DataLocation::try_from(result.data_location).map_or_else(
+                        |_| result.data_location.to_string(),
+                        |location| location.as_str_name().to_string(),
+                    );
+                    self.da_submissions.push(DaSubmissionRecord {
+                        proof_id: result.proof_id,
+                        height: self.height,
+                        tx_hash: tx_hash.to_string(),
+                        submitter: msg.creator.to_string(),
+                        namespace: result.namespace,
+                        data_location,
+                        da_block_hash: result.block_hash,
+                        da_block_height: result.block_height,
+                        da_tx_hash: result.tx_hash,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty() && self.verifications.is_empty() && self.da_submissions.is_empty()
+    }
+}
+
+/// Persistent storage for the indexer.
+pub trait IndexStore {
+    /// The last fully indexed height, or `None` before the first block.
+    fn checkpoint(&self) -> Result<Option<u64>>;
+
+    /// Stores `block`'s records and moves the checkpoint to its height, atomically.
+    fn write_block(&mut self, block: &IndexedBlock) -> Result<()>;
+
+    fn proof(&self, proof_id: &str) -> Result<Option<ProofRecord>>;
+
+    fn proofs_by_namespace(&self, namespace: &str) -> Result<Vec<ProofRecord>>;
+
+    fn verifications(&self, proof_id: &str) -> Result<Vec<VerificationRecord>>;
+
+    fn da_submissions(&self, proof_id: &str) -> Result<Vec<DaSubmissionRecord>>;
+}
+
+/// An [`IndexStore`] that keeps everything in memory, for tests and short-lived tools.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryStore {
+    checkpoint: Option<u64>,
+    proofs: BTreeMap<String, ProofRecord>,
+    verifications: Vec<VerificationRecord>,
+    da_submissions: Vec<DaSubmissionRecord>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IndexStore for InMemoryStore {
+    fn checkpoint(&self) -> Result<Option<u64>> {
+        Ok(self.checkpoint)
+    }
+
+    fn write_block(&mut self, block: &IndexedBlock) -> Result<()> {
+        for proof in &block.proofs {
+            self.proofs.insert(proof.proof_id.clone(), proof.clone());
+        }
+        self.verifications
+            .extend(block.verifications.iter().cloned());
+        self.da_submissions
+            .extend(block.da_submissions.iter().cloned());
+        self.checkpoint = Some(block.height);
+        Ok(())
+    }
+
+    fn proof(&self, proof_id: &str) -> Result<Option<ProofRecord>> {
+        Ok(self.proofs.get(proof_id).cloned())
+    }
+
+    fn proofs_by_namespace(&self, namespace: &str) -> Result<Vec<ProofRecord>> {
+        Ok(self
+            .proofs
+            .values()
+            .filter(|proof| proof.namespace == namespace)
+            .cloned()
+            .collect())
+    }
+
+    fn verifications(&self, proof_id: &str) -> Result<Vec<VerificationRecord>> {
+        Ok(self
+            .verifications
+            .iter()
+            .filter(|verification| verification.proof_id == proof_id)
+            .cloned()
+            .collect())
+    }
+
+    fn da_submissions(&self, proof_id: &str) -> Result<Vec<DaSubmissionRecord>> {
+        Ok(self
+            .da_submissions
+            .iter()
+            .filter(|submission| submission.proof_id == proof_id)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Walks blocks from a start height and writes their zkpverify records to a store. Failed
+/// polls in [`Indexer::run`] are passed to the [`ErrorHook`] set with
+/// [`Indexer::with_error_hook`].
+pub struct Indexer<S> {
+    chain_client: ChainClient,
+    tx_query_client: TxQueryClient,
+    store: S,
+    start_height: u64,
+    poll_interval: Duration,
+    batch_size: u64,
+    error_hook: ErrorHook,
+}
+
+impl<S: IndexStore> Indexer<S> {
+    pub fn new(rpc: &str, store: S) -> Self {
+        Self {
+            chain_client: ChainClient::new(rpc),
+            tx_query_client: TxQueryClient::new(rpc),
+            store,
+            start_height: 1,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            batch_size: DEFAULT_BATCH_SIZE,
+            error_hook: ErrorHook::default(),
+        }
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.chain_client = self.chain_client.with_nodes(nodes.clone());
+        self.tx_query_client = self.tx_query_client.with_nodes(nodes);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.chain_client = self.chain_client.with_retry_policy(retry_policy.clone());
+        self.tx_query_client = self.tx_query_client.with_retry_policy(retry_policy);
+        self
+    }
+
+    /// The first height to index when the store has no checkpoint yet.
+    pub fn with_start_height(mut self, start_height: u64) -> Self {
+        self.start_height = start_height.max(1);
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_error_hook(mut self, error_hook: ErrorHook) -> Self {
+        self.error_hook = error_hook;
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// The next height to index.
+    pub fn next_height(&self) -> Result<u64> {
+        Ok(self
+            .store
+            .checkpoint()?
+            .map_or(self.start_height, |checkpoint| checkpoint + 1))
+    }
+
+    /// Indexes until `shutdown` resolves, checking it between batches. A block in progress is
+    /// always finished.
+    pub async fn run(&mut self, shutdown: impl Future<Output = ()>) -> Result<()> {
+        tokio::pin!(shutdown);
+        loop {
+            let caught_up = match self.sync_once().await {
+                Ok(indexed) => indexed < self.batch_size,
+                Err(e) => {
+                    self.error_hook
+                        .report(&ErrorReport::msg(format!("Indexer poll failed: {}", e)));
+                    true
+                }
+            };
+            // While catching up the next batch starts at once, but shutdown still comes first.
+            let pause = if caught_up {
+                self.poll_interval
+            } else {
+                Duration::ZERO
+            };
+            tokio::select! {
+                biased;
+                _ = &mut shutdown => return Ok(()),
+                _ = tokio::time::sleep(pause) => {}
+            }
+        }
+    }
+
+    /// Indexes up to `batch_size` blocks from the checkpoint towards the latest height,
+    /// returning how many were indexed.
+    pub async fn sync_once(&mut self) -> Result<u64> {
+        let latest_height = self.chain_client.get_latest_height().await?;
+        let from = self.next_height()?;
+        if from > latest_height {
+            return Ok(0);
+        }
+        let to = latest_height.min(from + self.batch_size - 1);
+        for height in from..=to {
+            let block = self.index_block(height).await?;
+            self.store.write_block(&block)?;
+        }
+        Ok(to - from + 1)
+    }
+
+    /// Fetches and decodes the block at `height` without storing it.
+    pub async fn index_block(&self, height: u64) -> Result<IndexedBlock> {
+        let block = self.chain_client.get_block_by_height(height).await?;
+        if block.txs.is_empty() {
+            return IndexedBlock::from_txs(height, block.time, &[]);
+        }
+        // Block data has no execution results; the tx search adds them so that failed
+        // transactions can be skipped.
+        let mut search = TxSearch::new().with_height(height);
+        let mut txs = vec![];
+        loop {
+            let page = self.tx_query_client.search_txs(&search).await?;
+            let has_next = page.has_next() && !page.txs.is_empty();
+            txs.extend(page.txs);
+            if !has_next {
+                break;
+            }
+            search = search.next_page();
+        }
+        // A short result would silently drop records and still advance the checkpoint.
+        if txs.len() != block.txs.len() {
+            return Err(ErrorReport::msg(format!(
+                "Tx search found {} of the {} transactions in block {}",
+                txs.len(),
+                block.txs.len(),
+                height
+            )));
+        }
+        IndexedBlock::from_txs(height, block.time, &txs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InMemoryStore, IndexStore, IndexedBlock, Indexer};
+    use crate::{
+        authz::MsgExec,
+        decoder::{DecodedTx, TxResult},
+        error_hook::ErrorHook,
+        retry::RetryPolicy,
+        tx::TxClient,
+        types::{
+            DaSubmissionResult, MsgSubmitCommunityVerification, MsgSubmitProof,
+            MsgUpdateDASubmissionResults,
+        },
+    };
+    use cosmrs::{tx::Msg, Any};
+    use std::sync::{Arc, Mutex};
+
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const TEST_DATA: &str = "test-data";
+    const BITVM_PROOF_ID: &str = "8a17276c37500fe1f0b277f21205592eac037b60f8a7021713ed2b99fe4f78f2";
+
+    fn msg_submit_proof(tx_client: &TxClient) -> MsgSubmitProof {
+        let location = std::env::current_dir().unwrap().join(TEST_DATA);
+        MsgSubmitProof {
+            creator: tx_client.account_id(),
+            proof_system: "GROTH16_BN254_BITVM".to_string(),
+            proof: std::fs::read(location.join("proof.bitvm")).unwrap(),
+            public_input: std::fs::read(location.join("public_input.bitvm")).unwrap(),
+            vk: std::fs::read(location.join("vk.bitvm")).unwrap(),
+            namespace: "test-namespace".to_string(),
+            data_location: "FIAMMA".to_string(),
+        }
+    }
+
+    async fn decoded_tx(tx_client: &TxClient, msgs: Vec<Any>, code: u32) -> DecodedTx {
+        let raw = tx_client.sign_tx(msgs, 0, 0).unwrap();
+        let mut tx = DecodedTx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        tx.result = Some(TxResult {
+            height: 10,
+            code,
+            codespace: String::new(),
+            raw_log: String::new(),
+            gas_wanted: 0,
+            gas_used: 0,
+            events: vec![],
+        });
+        tx
+    }
+
+    #[tokio::test]
+    async fn index_block_records() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let submit_proof = msg_submit_proof(&tx_client);
+        let proof_id = submit_proof.proof_id();
+        assert_eq!(proof_id, BITVM_PROOF_ID);
+        let vote = MsgSubmitCommunityVerification {
+            creator: tx_client.account_id(),
+            proof_id: proof_id.clone(),
+            verify_result: true,
+        };
+        let da_results = MsgUpdateDASubmissionResults {
+            creator: tx_client.account_id(),
+            da_submission_results: vec![DaSubmissionResult {
+                proof_id: proof_id.clone(),
+                data_location: 0,
+                block_hash: "hash".to_string(),
+                block_height: 7,
+                tx_hash: "da-tx".to_string(),
+                namespace: "test-namespace".to_string(),
+            }],
+        };
+        let exec = MsgExec {
+            grantee: tx_client.account_id(),
+            msgs: vec![submit_proof.to_any().unwrap()],
+        };
+        let txs = vec![
+            decoded_tx(&tx_client, vec![exec.to_any().unwrap()], 0).await,
+            decoded_tx(
+                &tx_client,
+                vec![vote.to_any().unwrap(), da_results.to_any().unwrap()],
+                0,
+            )
+            .await,
+            decoded_tx(&tx_client, vec![vote.to_any().unwrap()], 5).await,
+        ];
+
+        let block = IndexedBlock::from_txs(10, None, &txs).unwrap();
+        assert_eq!(block.proofs.len(), 1);
+        assert_eq!(block.proofs[0].proof_id, proof_id);
+        assert_eq!(block.proofs[0].tx_hash, txs[0].hash);
+        // The failed third transaction is skipped.
+        assert_eq!(block.verifications.len(), 1);
+        assert_eq!(block.da_submissions.len(), 1);
+        assert_eq!(block.da_submissions[0].da_block_height, 7);
+
+        let mut store = InMemoryStore::new();
+        store.write_block(&block).unwrap();
+        assert_eq!(store.checkpoint().unwrap(), Some(10));
+        assert_eq!(
+            store.proof(&proof_id).unwrap(),
+            Some(block.proofs[0].clone())
+        );
+        assert_eq!(
+            store.proofs_by_namespace("test-namespace").unwrap().len(),
+            1
+        );
+        assert_eq!(store.verifications(&proof_id).unwrap().len(), 1);
+        assert_eq!(store.da_submissions(&proof_id).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn resume_from_checkpoint() {
+        let mut store = InMemoryStore::new();
+        let indexer = Indexer::new(UNREACHABLE_NODE, store.clone()).with_start_height(100);
+        assert_eq!(indexer.next_height().unwrap(), 100);
+
+        store
+            .write_block(&IndexedBlock {
+                height: 120,
+                ..Default::default()
+            })
+            .unwrap();
+        let mut indexer = Indexer::new(UNREACHABLE_NODE, store)
+            .with_start_height(100)
+            .with_retry_policy(RetryPolicy::none());
+        assert_eq!(indexer.next_height().unwrap(), 121);
+        assert!(indexer.sync_once().await.is_err());
+        assert_eq!(indexer.store().checkpoint().unwrap(), Some(120));
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let sink = errors.clone();
+        let mut indexer = indexer.with_error_hook(ErrorHook::new(move |e| {
+            sink.lock().unwrap().push(e.to_string())
+        }));
+        indexer.run(async {}).await.unwrap();
+        assert_eq!(errors.lock().unwrap().len(), 1);
+        assert!(errors.lock().unwrap()[0].starts_with("Indexer poll failed"));
+    }
+}
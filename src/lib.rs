@@ -1,9 +1,41 @@
 #![allow(dead_code)]
 
+pub mod amino;
+pub mod authz;
+pub mod authz_query;
+pub mod bank_query;
 pub mod bitvmstaker_query;
 pub mod chain;
+pub mod chain_query;
+#[cfg(feature = "local-verifier")]
+pub mod challenger;
+pub mod community_verifier;
+pub mod da;
+#[cfg(feature = "da-backends")]
+pub mod da_backends;
+pub mod decoder;
+pub mod error_hook;
+pub mod fee_payer;
+pub mod feegrant_query;
 pub(crate) mod generated;
+pub mod height;
+pub mod indexer;
+#[cfg(feature = "local-verifier")]
+pub mod local_verifier;
+pub mod multisig;
+pub mod namespace;
+pub mod node_pool;
+pub mod proof_artifacts;
+pub mod retry;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_helpers;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store;
+pub mod staker_set;
 pub mod tx;
+pub mod tx_query;
 pub mod types;
+pub mod validation;
+pub mod vk_registry;
 pub mod wallet;
 pub mod zkpverify_query;
@@ -0,0 +1,84 @@
+use crate::types::{MsgSubmitProof, ProofSystem};
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use cosmrs::{ErrorReport, Result};
+
+/// What `TxClient::submit_proof` does when a proof fails local verification.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LocalVerification {
+    #[default]
+    Off,
+    Warn,
+    Refuse,
+}
+
+/// Verifies a Groth16 BN254 proof in the arkworks compressed encoding used by the
+/// `*.bitvm` artifacts (see `test-data/`).
+pub fn verify_groth16(proof: &[u8], public_input: &[u8], vk: &[u8]) -> Result<bool> {
+    let proof = Proof::<Bn254>::deserialize_compressed(proof)
+        .map_err(|e| ErrorReport::msg(format!("Failed to parse groth16 proof: {}", e)))?;
+    let public_input = Vec::<Fr>::deserialize_compressed(public_input)
+        .map_err(|e| ErrorReport::msg(format!("Failed to parse public input: {}", e)))?;
+    let vk = VerifyingKey::<Bn254>::deserialize_compressed(vk)
+        .map_err(|e| ErrorReport::msg(format!("Failed to parse groth16 vk: {}", e)))?;
+    if vk.gamma_abc_g1.len() != public_input.len() + 1 {
+        return Ok(false);
+    }
+
+    let pvk = prepare_verifying_key(&vk);
+    Groth16::<Bn254>::verify_proof(&pvk, &proof, &public_input)
+        .map_err(|e| ErrorReport::msg(format!("Failed to verify groth16 proof: {}", e)))
+}
+
+impl MsgSubmitProof {
+    pub fn verify_locally(&self) -> Result<bool> {
+        match ProofSystem::from_str_name(&self.proof_system) {
+            Some(ProofSystem::Groth16Bn254Bitvm) => {
+                verify_groth16(&self.proof, &self.public_input, &self.vk)
+            }
+            Some(proof_system) => Err(ErrorReport::msg(format!(
+                "Local verification is not supported for {}",
+                proof_system.as_str_name()
+            ))),
+            None => Err(ErrorReport::msg(format!(
+                "Unknown proof system {}",
+                self.proof_system
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify_groth16;
+
+    const TEST_DATA: &str = "test-data";
+
+    fn proof_artifacts() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let location = std::env::current_dir().unwrap().join(TEST_DATA);
+        let proof = std::fs::read(location.join("proof.bitvm")).unwrap();
+        let public_input = std::fs::read(location.join("public_input.bitvm")).unwrap();
+        let vk = std::fs::read(location.join("vk.bitvm")).unwrap();
+        (proof, public_input, vk)
+    }
+
+    #[test]
+    fn verify_test_data() {
+        let (proof, public_input, vk) = proof_artifacts();
+        assert!(verify_groth16(&proof, &public_input, &vk).unwrap());
+    }
+
+    #[test]
+    fn reject_wrong_public_input() {
+        let (proof, mut public_input, vk) = proof_artifacts();
+        public_input[8] ^= 1;
+        assert!(!verify_groth16(&proof, &public_input, &vk).unwrap());
+    }
+
+    #[test]
+    fn reject_malformed_proof() {
+        let (proof, public_input, vk) = proof_artifacts();
+        assert!(verify_groth16(&proof[1..], &public_input, &vk).is_err());
+    }
+}
@@ -0,0 +1,413 @@
+use crate::{amino::StdSignDoc, chain::ACCOUNT_PREFIX, wallet::Wallet};
+use cosmos_sdk_proto::cosmos::{crypto::multisig::v1beta1::MultiSignature, tx::v1beta1::TxRaw};
+use cosmrs::{
+    crypto::{CompactBitArray, LegacyAminoMultisig, PublicKey},
+    proto::traits::MessageExt,
+    tendermint::chain::Id as ChainId,
+    tx::{
+        mode_info::Multi, AccountNumber, AuthInfo, Body, Fee, ModeInfo, Raw, SequenceNumber,
+        SignDoc, SignMode, SignerInfo,
+    },
+    AccountId, ErrorReport, Result,
+};
+use k256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+const MULTISIG_AMINO_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+const SECP256K1_AMINO_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+/// A legacy amino threshold multisig account, as created by `fiammad keys add --multisig`.
+///
+/// The order of `public_keys` is significant: it determines the account address and the
+/// position of each member's signature, so it must match the order used on-chain.
+#[derive(Debug, Clone)]
+pub struct MultisigAccount {
+    pub public_key: LegacyAminoMultisig,
+    pub account_id: AccountId,
+}
+
+impl MultisigAccount {
+    pub fn new(threshold: u32, public_keys: Vec<PublicKey>) -> Result<Self> {
+        if threshold == 0 {
+            return Err(ErrorReport::msg(
+                "Multisig threshold must be greater than 0",
+            ));
+        }
+        if threshold as usize > public_keys.len() {
+            return Err(ErrorReport::msg(format!(
+                "Multisig threshold {} exceeds number of public keys {}",
+                threshold,
+                public_keys.len()
+            )));
+        }
+        if public_keys
+            .iter()
+            .any(|pk| pk.type_url() != PublicKey::SECP256K1_TYPE_URL)
+        {
+            return Err(ErrorReport::msg(
+                "Multisig members must be secp256k1 public keys",
+            ));
+        }
+
+        let public_key = LegacyAminoMultisig {
+            threshold,
+            public_keys,
+        };
+        let hash = Sha256::digest(amino_encode(&public_key));
+        let account_id = AccountId::new(ACCOUNT_PREFIX, &hash[..20])?;
+        Ok(Self {
+            public_key,
+            account_id,
+        })
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.public_key.threshold
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_key.public_keys
+    }
+
+    pub fn amino_bytes(&self) -> Vec<u8> {
+        amino_encode(&self.public_key)
+    }
+
+    pub fn member_index(&self, public_key: &PublicKey) -> Option<usize> {
+        self.public_keys().iter().position(|pk| pk == public_key)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartialSignature {
+    pub public_key: PublicKey,
+    pub signature: Vec<u8>,
+}
+
+/// An unsigned multisig transaction together with the members that will sign it.
+///
+/// The signing members are fixed up front because `SIGN_MODE_DIRECT` covers the auth info,
+/// which records which members of the multisig signed. With `SIGN_MODE_LEGACY_AMINO_JSON`
+/// members sign `amino_sign_doc` instead.
+#[derive(Debug, Clone)]
+pub struct MultisigTx {
+    pub account: MultisigAccount,
+    pub sign_doc: SignDoc,
+    pub amino_sign_doc: Option<StdSignDoc>,
+    signers: Vec<usize>,
+}
+
+impl MultisigTx {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        account: &MultisigAccount,
+        signers: &[PublicKey],
+        body: &Body,
+        fee: Fee,
+        chain_id: &ChainId,
+        account_number: AccountNumber,
+        sequence: SequenceNumber,
+        sign_mode: SignMode,
+    ) -> Result<Self> {
+        let amino_sign_doc = match sign_mode {
+            SignMode::Direct => None,
+            SignMode::LegacyAminoJson => Some(StdSignDoc::new(
+                body,
+                &fee,
+                chain_id.as_str(),
+                account_number,
+                sequence,
+            )?),
+            mode => {
+                return Err(ErrorReport::msg(format!(
+                    "Unsupported sign mode {}",
+                    mode.as_str_name()
+                )))
+            }
+        };
+
+        let mut indices = signers
+            .iter()
+            .map(|pk| {
+                account.member_index(pk).ok_or(ErrorReport::msg(format!(
+                    "Public key {} is not a member of multisig {}",
+                    pk.to_json(),
+                    account.account_id
+                )))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.len() < account.threshold() as usize {
+            return Err(ErrorReport::msg(format!(
+                "Multisig requires {} signers, got {}",
+                account.threshold(),
+                indices.len()
+            )));
+        }
+
+        let n = account.public_keys().len();
+        let mut elems = vec![0u8; n.div_ceil(8)];
+        for &i in &indices {
+            elems[i / 8] |= 1 << (7 - (i % 8));
+        }
+        let signer_info = SignerInfo {
+            public_key: Some(account.public_key.clone().into()),
+            mode_info: ModeInfo::Multi(Multi {
+                bitarray: CompactBitArray::new((n % 8) as u32, elems),
+                mode_infos: vec![ModeInfo::single(sign_mode); indices.len()],
+            }),
+            sequence,
+        };
+        let auth_info = AuthInfo {
+            signer_infos: vec![signer_info],
+            fee,
+        };
+        let sign_doc = SignDoc::new(body, &auth_info, chain_id, account_number)?;
+
+        Ok(Self {
+            account: account.clone(),
+            sign_doc,
+            amino_sign_doc,
+            signers: indices,
+        })
+    }
+
+    pub fn signers(&self) -> Vec<PublicKey> {
+        self.signers
+            .iter()
+            .map(|&i| self.account.public_keys()[i])
+            .collect()
+    }
+
+    pub fn sign_bytes(&self) -> Result<Vec<u8>> {
+        match &self.amino_sign_doc {
+            Some(amino_sign_doc) => amino_sign_doc.sign_bytes(),
+            None => self.sign_doc.clone().into_bytes(),
+        }
+    }
+
+    pub fn sign(&self, wallet: &Wallet) -> Result<PartialSignature> {
+        self.signer_position(&wallet.public_key)?;
+        Ok(PartialSignature {
+            public_key: wallet.public_key,
+            signature: wallet.sign_bytes(&self.sign_bytes()?)?,
+        })
+    }
+
+    pub fn aggregate(self, signatures: Vec<PartialSignature>) -> Result<Raw> {
+        let sign_bytes = self.sign_bytes()?;
+        let mut ordered: Vec<Option<Vec<u8>>> = vec![None; self.signers.len()];
+        for partial in signatures {
+            let position = self.signer_position(&partial.public_key)?;
+            verify_signature(&partial.public_key, &sign_bytes, &partial.signature)?;
+            ordered[position] = Some(partial.signature);
+        }
+        let signatures = ordered
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ErrorReport::msg("Missing signature from a declared signer"))?;
+
+        let multi_signature = MultiSignature { signatures }.to_bytes()?;
+        Ok(TxRaw {
+            body_bytes: self.sign_doc.body_bytes,
+            auth_info_bytes: self.sign_doc.auth_info_bytes,
+            signatures: vec![multi_signature],
+        }
+        .into())
+    }
+
+    fn signer_position(&self, public_key: &PublicKey) -> Result<usize> {
+        self.account
+            .member_index(public_key)
+            .and_then(|i| self.signers.iter().position(|&s| s == i))
+            .ok_or(ErrorReport::msg(format!(
+                "Public key {} is not a declared signer of this transaction",
+                public_key.to_json()
+            )))
+    }
+}
+
+pub(crate) fn verify_signature(public_key: &PublicKey, msg: &[u8], signature: &[u8]) -> Result<()> {
+    let verifying_key = VerifyingKey::from_sec1_bytes(&public_key.to_bytes())?;
+    let signature = Signature::from_slice(signature)?;
+    verifying_key.verify(msg, &signature)?;
+    Ok(())
+}
+
+fn amino_encode(multisig: &LegacyAminoMultisig) -> Vec<u8> {
+    let mut bytes = MULTISIG_AMINO_PREFIX.to_vec();
+    bytes.push(0x08);
+    encode_uvarint(multisig.threshold as u64, &mut bytes);
+    for public_key in &multisig.public_keys {
+        let key = public_key.to_bytes();
+        let mut encoded = SECP256K1_AMINO_PREFIX.to_vec();
+        encode_uvarint(key.len() as u64, &mut encoded);
+        encoded.extend_from_slice(&key);
+
+        bytes.push(0x12);
+        encode_uvarint(encoded.len() as u64, &mut bytes);
+        bytes.extend_from_slice(&encoded);
+    }
+    bytes
+}
+
+fn encode_uvarint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultisigAccount, MultisigTx};
+    use crate::{chain::*, types::MsgCreateStaker, wallet::Wallet};
+    use cosmos_sdk_proto::cosmos::{
+        crypto::multisig::v1beta1::MultiSignature,
+        tx::v1beta1::{
+            mode_info::{Single, Sum},
+            AuthInfo, TxRaw,
+        },
+    };
+    use cosmrs::{
+        proto::traits::Message,
+        tx::{BodyBuilder, Fee, Msg, SignMode},
+        Coin,
+    };
+
+    const MEMBER_KEYS: [&str; 3] = [
+        "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8",
+        "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897",
+        "1f0c9bde7f9e9a3bbcab6a4e2b3d1a0f7c3b5e9d8a6f4c2e0b1d3f5a7c9e8b6d",
+    ];
+
+    fn members() -> Vec<Wallet> {
+        MEMBER_KEYS.iter().map(|key| Wallet::new(key)).collect()
+    }
+
+    fn multisig_tx(
+        account: &MultisigAccount,
+        signers: &[&Wallet],
+        sign_mode: SignMode,
+    ) -> MultisigTx {
+        let msg = MsgCreateStaker {
+            creator: account.account_id.clone(),
+            staker_address: "fiammavaloper124lzt3g4axrqf8p58f6hs9uzdk056y3cey2m5r".to_string(),
+        };
+        let body = BodyBuilder::new().msg(msg.to_any().unwrap()).finish();
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                amount: 2000,
+                denom: DENOM.parse().unwrap(),
+            },
+            200_000_u64,
+        );
+        let signers: Vec<_> = signers.iter().map(|w| w.public_key).collect();
+        MultisigTx::new(
+            account,
+            &signers,
+            &body,
+            fee,
+            &CHAIN_ID.parse().unwrap(),
+            7,
+            3,
+            sign_mode,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn multisig_account() {
+        let members = members();
+        let public_keys: Vec<_> = members.iter().map(|w| w.public_key).collect();
+        let account = MultisigAccount::new(2, public_keys.clone()).unwrap();
+        assert!(account.account_id.as_ref().starts_with(ACCOUNT_PREFIX));
+
+        let amino = account.amino_bytes();
+        assert_eq!(&amino[..6], &[0x22, 0xc1, 0xf7, 0xe2, 0x08, 0x02]);
+        assert_eq!(amino.len(), 6 + 3 * (2 + 4 + 1 + 33));
+
+        let reordered = vec![public_keys[1], public_keys[0], public_keys[2]];
+        let other = MultisigAccount::new(2, reordered).unwrap();
+        assert_ne!(account.account_id, other.account_id);
+
+        assert!(MultisigAccount::new(0, public_keys.clone()).is_err());
+        assert!(MultisigAccount::new(4, public_keys).is_err());
+    }
+
+    #[test]
+    fn multisig_sign_and_aggregate() {
+        let members = members();
+        let public_keys: Vec<_> = members.iter().map(|w| w.public_key).collect();
+        let account = MultisigAccount::new(2, public_keys).unwrap();
+        let tx = multisig_tx(&account, &[&members[2], &members[0]], SignMode::Direct);
+
+        assert!(tx.sign(&members[1]).is_err());
+        let signatures = vec![tx.sign(&members[2]).unwrap(), tx.sign(&members[0]).unwrap()];
+        let raw = tx.clone().aggregate(signatures.clone()).unwrap();
+
+        let tx_raw = TxRaw::decode(raw.to_bytes().unwrap().as_slice()).unwrap();
+        assert_eq!(tx_raw.signatures.len(), 1);
+        let multi = MultiSignature::decode(tx_raw.signatures[0].as_slice()).unwrap();
+        assert_eq!(
+            multi.signatures,
+            vec![
+                signatures[1].signature.clone(),
+                signatures[0].signature.clone()
+            ]
+        );
+
+        let auth_info = AuthInfo::decode(tx_raw.auth_info_bytes.as_slice()).unwrap();
+        let signer_info = &auth_info.signer_infos[0];
+        assert_eq!(signer_info.sequence, 3);
+        match signer_info.mode_info.clone().unwrap().sum.unwrap() {
+            Sum::Multi(multi) => {
+                let bitarray = multi.bitarray.unwrap();
+                assert_eq!(bitarray.extra_bits_stored, 3);
+                assert_eq!(bitarray.elems, vec![0b1010_0000]);
+                assert_eq!(multi.mode_infos.len(), 2);
+            }
+            Sum::Single(_) => panic!("expected multi mode info"),
+        }
+
+        assert!(tx.clone().aggregate(signatures[..1].to_vec()).is_err());
+        let mut forged = signatures.clone();
+        forged[0].signature = forged[1].signature.clone();
+        assert!(tx.aggregate(forged).is_err());
+    }
+
+    #[test]
+    fn multisig_amino_json() {
+        let members = members();
+        let public_keys: Vec<_> = members.iter().map(|w| w.public_key).collect();
+        let account = MultisigAccount::new(2, public_keys).unwrap();
+        let tx = multisig_tx(
+            &account,
+            &[&members[0], &members[1]],
+            SignMode::LegacyAminoJson,
+        );
+
+        let amino_bytes = tx.amino_sign_doc.clone().unwrap().sign_bytes().unwrap();
+        assert_eq!(tx.sign_bytes().unwrap(), amino_bytes);
+
+        let signatures = vec![tx.sign(&members[0]).unwrap(), tx.sign(&members[1]).unwrap()];
+        let raw = tx.aggregate(signatures).unwrap();
+        let tx_raw = TxRaw::decode(raw.to_bytes().unwrap().as_slice()).unwrap();
+        let auth_info = AuthInfo::decode(tx_raw.auth_info_bytes.as_slice()).unwrap();
+        match auth_info.signer_infos[0]
+            .mode_info
+            .clone()
+            .unwrap()
+            .sum
+            .unwrap()
+        {
+            Sum::Multi(multi) => assert!(multi.mode_infos.iter().all(|mode_info| matches!(
+                mode_info.sum,
+                Some(Sum::Single(Single { mode })) if mode == SignMode::LegacyAminoJson as i32
+            ))),
+            Sum::Single(_) => panic!("expected multi mode info"),
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    tx::TxClient,
+    types::{MsgSubmitProof, VerificationStatus, VerifyResult},
+    zkpverify_query::QueryClient,
+};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxResponse;
+use cosmrs::{ErrorReport, Result};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Counts over the proofs a [`NamespaceClient`] has submitted or seen in query results.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceStats {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    /// Proofs that reached hard finality.
+    pub finalized: usize,
+    /// Mean time from submission to the first query that saw hard finality, over proofs
+    /// submitted through this client. Resolution is bounded by how often results are queried.
+    pub average_time_to_finality: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct TrackedProof {
+    result: Option<bool>,
+    status: Option<VerificationStatus>,
+    submitted_at: Option<Instant>,
+    time_to_finality: Option<Duration>,
+}
+
+/// Submits and queries proofs under a single namespace, keeping statistics for it.
+pub struct NamespaceClient {
+    namespace: String,
+    tx_client: TxClient,
+    query_client: QueryClient,
+    proofs: HashMap<String, TrackedProof>,
+}
+
+impl NamespaceClient {
+    pub fn new(tx_client: TxClient, namespace: &str) -> Self {
+        let query_client = QueryClient::from_nodes(tx_client.nodes.clone())
+            .with_retry_policy(tx_client.retry_policy.clone());
+        Self {
+            namespace: namespace.to_string(),
+            tx_client,
+            query_client,
+            proofs: HashMap::new(),
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// A `MsgSubmitProof` from this client's account under its namespace.
+    pub fn msg(
+        &self,
+        proof_system: &str,
+        proof: Vec<u8>,
+        public_input: Vec<u8>,
+        vk: Vec<u8>,
+        data_location: &str,
+    ) -> MsgSubmitProof {
+        MsgSubmitProof {
+            creator: self.tx_client.account_id(),
+            proof_system: proof_system.to_string(),
+            proof,
+            public_input,
+            vk,
+            namespace: self.namespace.clone(),
+            data_location: data_location.to_string(),
+        }
+    }
+
+    /// The ID the chain assigns a proof submitted under this namespace.
+    pub fn proof_id(
+        &self,
+        proof_system: &str,
+        proof: &[u8],
+        public_input: &[u8],
+        vk: &[u8],
+    ) -> String {
+        self.msg(
+            proof_system,
+            proof.to_vec(),
+            public_input.to_vec(),
+            vk.to_vec(),
+            "",
+        )
+        .proof_id()
+    }
+
+    /// Submits `msg`, which must be under this client's namespace. Accepted proofs are
+    /// tracked for [`NamespaceClient::stats`].
+    pub async fn submit_proof(&mut self, msg: MsgSubmitProof) -> Result<BroadcastTxResponse> {
+        if msg.namespace != self.namespace {
+            return Err(ErrorReport::msg(format!(
+                "Proof namespace {} does not match client namespace {}",
+                msg.namespace, self.namespace
+            )));
+        }
+        let proof_id = msg.proof_id();
+        let resp = self.tx_client.submit_proof(msg).await?;
+        if resp.tx_response.as_ref().is_some_and(|tx| tx.code == 0) {
+            self.proofs.entry(proof_id).or_default().submitted_at = Some(Instant::now());
+        }
+        Ok(resp)
+    }
+
+    pub async fn get_verify_result(&mut self, proof_id: &str) -> Result<VerifyResult> {
+        let verify_result = self.query_client.get_verify_result(proof_id).await?;
+        if verify_result.namespace != self.namespace {
+            return Err(ErrorReport::msg(format!(
+                "Proof {} belongs to namespace {}",
+                proof_id, verify_result.namespace
+            )));
+        }
+        self.observe(&verify_result);
+        Ok(verify_result)
+    }
+
+    /// Verify results in this namespace. An empty list is not an error.
+    pub async fn get_verify_results(&mut self) -> Result<Vec<VerifyResult>> {
+        let verify_results = self.query_client.verify_results(&self.namespace).await?;
+        for result in &verify_results {
+            self.observe(result);
+        }
+        Ok(verify_results)
+    }
+
+    /// Pending proofs in this namespace. An empty list is not an error.
+    pub async fn get_pending_proofs(&mut self) -> Result<Vec<VerifyResult>> {
+        let pending_proofs = self
+            .query_client
+            .pending_proofs(Some(&self.namespace))
+            .await?;
+        for result in &pending_proofs {
+            self.observe(result);
+        }
+        Ok(pending_proofs)
+    }
+
+    pub fn stats(&self) -> NamespaceStats {
+        let mut stats = NamespaceStats {
+            total: self.proofs.len(),
+            ..Default::default()
+        };
+        let mut finality_times = vec![];
+        for proof in self.proofs.values() {
+            match proof.result {
+                Some(true) => stats.passed += 1,
+                Some(false) => stats.failed += 1,
+                None => {}
+            }
+            if proof.status == Some(VerificationStatus::HardFinality) {
+                stats.finalized += 1;
+            }
+            finality_times.extend(proof.time_to_finality);
+        }
+        if !finality_times.is_empty() {
+            let total: Duration = finality_times.iter().sum();
+            stats.average_time_to_finality = Some(total / finality_times.len() as u32);
+        }
+        stats
+    }
+
+    fn observe(&mut self, verify_result: &VerifyResult) {
+        if verify_result.namespace != self.namespace {
+            return;
+        }
+        let proof = self
+            .proofs
+            .entry(verify_result.proof_id.clone())
+            .or_default();
+        let status = VerificationStatus::try_from(verify_result.status).ok();
+        proof.result = Some(verify_result.result);
+        proof.status = status;
+        if status == Some(VerificationStatus::HardFinality) && proof.time_to_finality.is_none() {
+            proof.time_to_finality = proof.submitted_at.map(|at| at.elapsed());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NamespaceClient;
+    use crate::{
+        retry::RetryPolicy,
+        tx::TxClient,
+        types::{VerificationStatus, VerifyResult},
+    };
+    use std::time::{Duration, Instant};
+
+    const NODE: &str = "http://54.65.75.57:9090";
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+
+    fn verify_result(proof_id: &str, result: bool, status: VerificationStatus) -> VerifyResult {
+        VerifyResult {
+            proof_id: proof_id.to_string(),
+            result,
+            status: status as i32,
+            namespace: "test-namespace".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stats_track_namespace_results() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let mut client = NamespaceClient::new(tx_client, "test-namespace");
+        let submitted_at = Instant::now().checked_sub(Duration::from_secs(10)).unwrap();
+        client
+            .proofs
+            .entry("a".to_string())
+            .or_default()
+            .submitted_at = Some(submitted_at);
+
+        client.observe(&verify_result("a", true, VerificationStatus::SoftFinality));
+        client.observe(&verify_result("b", false, VerificationStatus::HardFinality));
+        client.observe(&VerifyResult {
+            namespace: "other".to_string(),
+            ..verify_result("c", true, VerificationStatus::HardFinality)
+        });
+        let stats = client.stats();
+        assert_eq!((stats.total, stats.passed, stats.failed), (2, 1, 1));
+        assert_eq!(stats.finalized, 1);
+        assert_eq!(stats.average_time_to_finality, None);
+
+        client.observe(&verify_result("a", true, VerificationStatus::HardFinality));
+        let stats = client.stats();
+        assert_eq!(stats.finalized, 2);
+        assert!(stats.average_time_to_finality.unwrap() >= Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn rejects_other_namespace() {
+        let tx_client = TxClient::new(PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000)
+            .with_retry_policy(RetryPolicy::none());
+        let mut client = NamespaceClient::new(tx_client, "test-namespace");
+        let mut msg = client.msg("GROTH16_BN254_BITVM", vec![1], vec![2], vec![3], "FIAMMA");
+        assert_eq!(msg.namespace, "test-namespace");
+        assert_eq!(
+            client.proof_id("GROTH16_BN254_BITVM", &[1], &[2], &[3]),
+            msg.proof_id()
+        );
+
+        msg.namespace = "other".to_string();
+        let err = client.submit_proof(msg).await.unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+        assert_eq!(client.stats().total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_results() {
+        let tx_client = TxClient::new(PRIVATE_KEY, NODE, 2000, 200_000);
+        let mut client = NamespaceClient::new(tx_client, "test-namespace");
+        let results = client.get_verify_results().await;
+        println!("verify results: {:?}", results);
+        println!("stats: {:?}", client.stats());
+    }
+}
@@ -0,0 +1,398 @@
+use crate::{
+    chain_query::BlockInfo,
+    retry::{self, RetryLayer, RetryPolicy, RetryService},
+};
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{
+    service_client::ServiceClient, GetLatestBlockRequest, GetSyncingRequest,
+};
+use cosmrs::{ErrorReport, Result};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, OnceLock},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tonic::{
+    body::BoxBody,
+    codegen::{http, StdError},
+    transport::{Body, Channel, Endpoint},
+    Code,
+};
+use tower::{Service, ServiceBuilder};
+
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+/// How many blocks a node may trail the highest known height and still count as healthy.
+pub const DEFAULT_MAX_LAG: u64 = 5;
+/// How long a node that failed a request is skipped before it is tried again.
+pub const DEFAULT_FAILURE_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeHealth {
+    /// `None` until the first health check.
+    pub last_checked: Option<Instant>,
+    pub reachable: bool,
+    pub syncing: bool,
+    pub latest_height: u64,
+    pub latency: Option<Duration>,
+    /// When a request to the node last failed at the transport level. The node is skipped
+    /// until the pool's failure cooldown has passed or a health check succeeds.
+    pub failed_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Node {
+    rpc: String,
+    channel: OnceLock<Channel>,
+    health: Mutex<NodeHealth>,
+}
+
+/// A set of gRPC endpoints for the same chain.
+///
+/// Requests go to the healthiest node: reachable, not syncing, within `max_lag` blocks of
+/// the highest known height, then lowest latency. Nodes that have not been health-checked
+/// yet keep their configured order. A node that fails a request is skipped for
+/// `failure_cooldown`, then tried again. Cloning is cheap and clones share health state.
+#[derive(Clone, Debug)]
+pub struct NodePool {
+    nodes: Arc<Vec<Node>>,
+    max_lag: u64,
+    health_check_timeout: Duration,
+    failure_cooldown: Duration,
+}
+
+impl NodePool {
+    pub fn new<S: AsRef<str>>(endpoints: &[S]) -> Self {
+        let nodes = endpoints
+            .iter()
+            .map(|rpc| Node {
+                rpc: rpc.as_ref().to_string(),
+                channel: OnceLock::new(),
+                health: Mutex::new(NodeHealth::default()),
+            })
+            .collect();
+        Self {
+            nodes: Arc::new(nodes),
+            max_lag: DEFAULT_MAX_LAG,
+            health_check_timeout: DEFAULT_HEALTH_CHECK_TIMEOUT,
+            failure_cooldown: DEFAULT_FAILURE_COOLDOWN,
+        }
+    }
+
+    pub fn with_max_lag(mut self, max_lag: u64) -> Self {
+        self.max_lag = max_lag;
+        self
+    }
+
+    pub fn with_health_check_timeout(mut self, health_check_timeout: Duration) -> Self {
+        self.health_check_timeout = health_check_timeout;
+        self
+    }
+
+    pub fn with_failure_cooldown(mut self, failure_cooldown: Duration) -> Self {
+        self.failure_cooldown = failure_cooldown;
+        self
+    }
+
+    pub fn endpoints(&self) -> Vec<String> {
+        self.nodes.iter().map(|node| node.rpc.clone()).collect()
+    }
+
+    /// The first configured endpoint.
+    pub fn primary(&self) -> &str {
+        self.nodes.first().map_or("", |node| &node.rpc)
+    }
+
+    pub fn health(&self) -> Vec<(String, NodeHealth)> {
+        self.nodes
+            .iter()
+            .map(|node| (node.rpc.clone(), node.health.lock().unwrap().clone()))
+            .collect()
+    }
+
+    /// Queries every node's latest block height and sync status.
+    pub async fn check_health(&self) {
+        let checks: Vec<_> = (0..self.nodes.len())
+            .map(|index| {
+                let pool = self.clone();
+                tokio::spawn(async move { (index, pool.check_node(index).await) })
+            })
+            .collect();
+        for check in checks {
+            if let Ok((index, health)) = check.await {
+                *self.nodes[index].health.lock().unwrap() = health;
+            }
+        }
+    }
+
+    /// Runs [`NodePool::check_health`] every `interval` until the returned handle is aborted.
+    pub fn spawn_health_checks(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let pool = self.clone();
+        tokio::spawn(async move {
+            loop {
+                pool.check_health().await;
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    async fn check_node(&self, index: usize) -> NodeHealth {
+        let started = Instant::now();
+        let status = async {
+            let mut client = ServiceClient::new(self.node_channel(index)?);
+            let syncing = client
+                .get_syncing(GetSyncingRequest {})
+                .await?
+                .into_inner()
+                .syncing;
+            let block = client
+                .get_latest_block(GetLatestBlockRequest {})
+                .await?
+                .into_inner();
+            let block = BlockInfo::from_parts(block.block_id, block.block, block.sdk_block)?;
+            Ok::<_, ErrorReport>((syncing, block.height))
+        };
+        let status = tokio::time::timeout(self.health_check_timeout, status).await;
+        match status {
+            Ok(Ok((syncing, latest_height))) => NodeHealth {
+                last_checked: Some(Instant::now()),
+                reachable: true,
+                syncing,
+                latest_height,
+                latency: Some(started.elapsed()),
+                failed_at: None,
+            },
+            _ => NodeHealth {
+                last_checked: Some(Instant::now()),
+                ..NodeHealth::default()
+            },
+        }
+    }
+
+    /// Node indices from healthiest to least healthy. Unhealthy nodes are kept at the end as
+    /// a last resort.
+    pub fn ranked(&self) -> Vec<usize> {
+        let health = self.snapshot();
+        let mut ranked: Vec<usize> = (0..health.len()).collect();
+        // Stable sort: ties keep the configured order.
+        ranked.sort_by_key(|&index| {
+            let h = &health[index];
+            (
+                !self.healthy(&health, index),
+                h.last_checked.is_none(),
+                h.latency.unwrap_or(Duration::MAX),
+            )
+        });
+        ranked
+    }
+
+    pub fn best(&self) -> Option<usize> {
+        self.ranked().first().copied()
+    }
+
+    pub fn is_healthy(&self, index: usize) -> bool {
+        index < self.nodes.len() && self.healthy(&self.snapshot(), index)
+    }
+
+    fn snapshot(&self) -> Vec<NodeHealth> {
+        self.nodes
+            .iter()
+            .map(|node| node.health.lock().unwrap().clone())
+            .collect()
+    }
+
+    fn healthy(&self, health: &[NodeHealth], index: usize) -> bool {
+        let h = &health[index];
+        let max_height = health.iter().map(|h| h.latest_height).max().unwrap_or(0);
+        let failed = h
+            .failed_at
+            .is_some_and(|failed_at| failed_at.elapsed() < self.failure_cooldown);
+        match h.last_checked {
+            None => !failed,
+            Some(_) => {
+                h.reachable && !failed && !h.syncing && h.latest_height + self.max_lag >= max_height
+            }
+        }
+    }
+
+    pub(crate) fn mark_failed(&self, index: usize) {
+        if let Some(node) = self.nodes.get(index) {
+            node.health.lock().unwrap().failed_at = Some(Instant::now());
+        }
+    }
+
+    pub(crate) fn node_channel(&self, index: usize) -> Result<Channel> {
+        let node = self
+            .nodes
+            .get(index)
+            .ok_or(ErrorReport::msg(format!("No node at index {}", index)))?;
+        if let Some(channel) = node.channel.get() {
+            return Ok(channel.clone());
+        }
+        let channel = Endpoint::from_shared(node.rpc.clone())?.connect_lazy();
+        Ok(node.channel.get_or_init(|| channel).clone())
+    }
+
+    /// A channel that routes each call to the healthiest node and fails over to the next
+    /// one, with `policy` applied on top.
+    pub(crate) fn channel(&self, policy: &RetryPolicy) -> RetryService<FailoverChannel> {
+        ServiceBuilder::new()
+            .layer(RetryLayer::new(policy.clone()))
+            .service(FailoverChannel {
+                pool: self.clone(),
+                policy: policy.clone(),
+            })
+    }
+
+    /// A channel to the node at `index` only.
+    pub(crate) fn pinned_channel(
+        &self,
+        index: usize,
+        policy: &RetryPolicy,
+    ) -> Result<RetryService<Channel>> {
+        Ok(ServiceBuilder::new()
+            .layer(RetryLayer::new(policy.clone()))
+            .service(self.node_channel(index)?))
+    }
+}
+
+/// Sends each call to the healthiest node in a [`NodePool`], trying the next node when one
+/// is unreachable or reports `Unavailable`. Non-idempotent calls only fail over when the
+/// connection was refused.
+#[derive(Clone, Debug)]
+pub struct FailoverChannel {
+    pool: NodePool,
+    policy: RetryPolicy,
+}
+
+impl Service<http::Request<BoxBody>> for FailoverChannel {
+    type Response = http::Response<Body>;
+    type Error = StdError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        let pool = self.pool.clone();
+        let policy = self.policy.clone();
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = retry::collect(body).await?;
+            let method = parts.uri.path().to_string();
+
+            let ranked = pool.ranked();
+            let mut last_error: StdError = "No endpoints configured".into();
+            for (position, &index) in ranked.iter().enumerate() {
+                let is_last = position + 1 == ranked.len();
+                let mut channel = pool.node_channel(index)?;
+                retry::wait_ready::<_, http::Request<BoxBody>>(&mut channel).await?;
+                match channel.call(retry::rebuild(&parts, body.clone())).await {
+                    Ok(response) => {
+                        let unavailable = retry::header_status(&response)
+                            .is_some_and(|code| code == Code::Unavailable);
+                        if !unavailable {
+                            return Ok(response);
+                        }
+                        pool.mark_failed(index);
+                        if is_last || !policy.should_retry_status(&method, Code::Unavailable) {
+                            return Ok(response);
+                        }
+                    }
+                    Err(error) => {
+                        let error: StdError = error.into();
+                        pool.mark_failed(index);
+                        if !policy.should_retry_error(&method, &error) {
+                            return Err(error);
+                        }
+                        last_error = error;
+                    }
+                }
+            }
+            Err(last_error)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NodeHealth, NodePool};
+    use crate::{retry::RetryPolicy, zkpverify_query::QueryClient};
+    use std::time::{Duration, Instant};
+
+    const UNREACHABLE_NODES: [&str; 2] = ["http://127.0.0.1:1", "http://127.0.0.1:2"];
+
+    fn set_health(pool: &NodePool, index: usize, health: NodeHealth) {
+        *pool.nodes[index].health.lock().unwrap() = health;
+    }
+
+    fn checked(latest_height: u64, latency_ms: u64) -> NodeHealth {
+        NodeHealth {
+            last_checked: Some(Instant::now()),
+            reachable: true,
+            syncing: false,
+            latest_height,
+            latency: Some(Duration::from_millis(latency_ms)),
+            failed_at: None,
+        }
+    }
+
+    #[test]
+    fn ranks_by_health() {
+        let pool = NodePool::new(&[
+            "http://a:9090",
+            "http://b:9090",
+            "http://c:9090",
+            "http://d:9090",
+        ]);
+        assert_eq!(pool.ranked(), vec![0, 1, 2, 3]);
+
+        // a lags too far behind, b is fastest, c is syncing, d is fine but slower than b.
+        set_health(&pool, 0, checked(90, 1));
+        set_health(&pool, 1, checked(100, 10));
+        set_health(
+            &pool,
+            2,
+            NodeHealth {
+                syncing: true,
+                ..checked(100, 1)
+            },
+        );
+        set_health(&pool, 3, checked(98, 20));
+        assert_eq!(pool.ranked(), vec![1, 3, 0, 2]);
+
+        pool.mark_failed(1);
+        assert_eq!(pool.best(), Some(3));
+    }
+
+    #[test]
+    fn failed_nodes_recover_after_cooldown() {
+        let pool = NodePool::new(&["http://a:9090", "http://b:9090"]);
+        pool.mark_failed(0);
+        assert_eq!(pool.best(), Some(1));
+
+        let recovered = pool.clone().with_failure_cooldown(Duration::ZERO);
+        assert!(recovered.is_healthy(0));
+        assert_eq!(recovered.best(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn unreachable_nodes_fail_over_and_are_marked() {
+        let pool = NodePool::new(&UNREACHABLE_NODES);
+        let query_client =
+            QueryClient::from_nodes(pool.clone()).with_retry_policy(RetryPolicy::none());
+        assert!(query_client.get_pending_proof().await.is_err());
+        assert!(pool
+            .health()
+            .iter()
+            .all(|(_, health)| health.failed_at.is_some()));
+
+        pool.check_health().await;
+        assert!(pool
+            .health()
+            .iter()
+            .all(|(_, health)| health.last_checked.is_some() && !health.reachable));
+    }
+}
@@ -0,0 +1,869 @@
+//! `GROTH16_BN254_BITVM` and `FFPLONK_BN254_BITVM` proof artifacts and converters from common
+//! prover outputs.
+//!
+//! For Groth16 the chain expects the arkworks compressed encoding, as in the `*.bitvm` files
+//! under `test-data/`:
+//!
+//! - `proof.bitvm`: `a: G1 || b: G2 || c: G1`, 128 bytes.
+//! - `vk.bitvm`: `alpha: G1 || beta: G2 || gamma: G2 || delta: G2 || n: u64 ||
+//!   gamma_abc: [G1; n]`, where `n` is the number of public inputs plus one.
+//! - `public_input.bitvm`: `n: u64 || [Fr; n]`.
+//!
+//! Integers and field elements are little endian, `Fr` takes 32 bytes. A G1 point is its `x`
+//! coordinate with bit 7 of the last byte set when `y > -y`. A G2 point is `x.c0 || x.c1` with
+//! the same flag in the last byte of `x.c1`, where `y` and `-y` are compared on `c1` first.
+//!
+//! FFLONK artifacts converted from snarkjs' `fflonk` output use the same point and field
+//! encodings and file names, with the layouts of [`FFLONK_PROOF_SIZE`] and [`FFLONK_VK_SIZE`].
+//! Those layouts are the SDK's own and have not been checked against the chain's FFLONK
+//! verifier, so loading and validating FFLONK artifacts leaves their bytes unchecked. The
+//! fixture under `test-data/fflonk/` has the shape of snarkjs output but was not produced by
+//! snarkjs and is not a valid proof.
+
+use crate::{
+    types::{MsgSubmitProof, ProofSystem},
+    validation::{
+        less_than_le, validate_fflonk_proof, validate_fflonk_vk, validate_groth16_proof,
+        validate_groth16_vk, validate_public_input, ValidationError, FFLONK_PROOF_SIZE,
+        FFLONK_VK_SIZE, FLAGS_MASK, FQ_MODULUS, FR_MODULUS, GROTH16_PROOF_SIZE,
+    },
+};
+use cosmrs::{AccountId, ErrorReport, Result};
+use serde_json::Value;
+use std::{fs, path::Path};
+
+pub const PROOF_FILE: &str = "proof.bitvm";
+pub const PUBLIC_INPUT_FILE: &str = "public_input.bitvm";
+pub const VK_FILE: &str = "vk.bitvm";
+
+const GROTH16_PROTOCOL: &str = "groth16";
+const FFLONK_PROTOCOL: &str = "fflonk";
+// snarkjs FFLONK proof fields, in the order of the chain encoding.
+const FFLONK_POLYNOMIALS: [&str; 4] = ["C1", "C2", "W1", "W2"];
+const FFLONK_EVALUATIONS: [&str; 16] = [
+    "ql", "qr", "qm", "qo", "qc", "s1", "s2", "s3", "a", "b", "c", "z", "zw", "t1w", "t2w", "inv",
+];
+const FFLONK_VK_ROOTS: [&str; 7] = ["k1", "k2", "w", "w3", "w4", "w8", "wr"];
+// arkworks flag for `y > -y` in compressed points.
+const Y_IS_NEGATIVE: u8 = 1 << 7;
+// gnark-crypto keeps its flags in the top two bits of the first (big endian) byte.
+const GNARK_UNCOMPRESSED: u8 = 0b00 << 6;
+const GNARK_COMPRESSED_SMALLEST: u8 = 0b10 << 6;
+const GNARK_COMPRESSED_LARGEST: u8 = 0b11 << 6;
+
+type Fq = [u8; 32];
+
+/// The proof, public input and VK of a proof submission.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProofArtifacts {
+    pub proof_system: ProofSystem,
+    pub proof: Vec<u8>,
+    pub public_input: Vec<u8>,
+    pub vk: Vec<u8>,
+}
+
+impl ProofArtifacts {
+    /// Reads Groth16 `proof.bitvm`, `public_input.bitvm` and `vk.bitvm` from `dir`, failing if
+    /// the proof is not [`GROTH16_PROOF_SIZE`] bytes. Use [`ProofArtifacts::load_as`] for
+    /// FFLONK artifacts.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let artifacts = Self::load_as(dir, ProofSystem::Groth16Bn254Bitvm)?;
+        if artifacts.proof.len() != GROTH16_PROOF_SIZE {
+            return Err(ErrorReport::msg(format!(
+                "{} is {} bytes, not a {} byte groth16 proof",
+                dir.join(PROOF_FILE).display(),
+                artifacts.proof.len(),
+                GROTH16_PROOF_SIZE
+            )));
+        }
+        Ok(artifacts)
+    }
+
+    /// Reads the artifacts of `proof_system` from `dir` as they are, without checking their
+    /// encoding.
+    pub fn load_as(dir: impl AsRef<Path>, proof_system: ProofSystem) -> Result<Self> {
+        let dir = dir.as_ref();
+        Ok(Self {
+            proof_system,
+            proof: read(&dir.join(PROOF_FILE))?,
+            public_input: read(&dir.join(PUBLIC_INPUT_FILE))?,
+            vk: read(&dir.join(VK_FILE))?,
+        })
+    }
+
+    /// Writes the artifacts to `dir` under the file names read by [`ProofArtifacts::load`].
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(PROOF_FILE), &self.proof)?;
+        fs::write(dir.join(PUBLIC_INPUT_FILE), &self.public_input)?;
+        fs::write(dir.join(VK_FILE), &self.vk)?;
+        Ok(())
+    }
+
+    /// Converts snarkjs `proof.json`, `public.json` and `verification_key.json` contents from
+    /// either `groth16` or `fflonk`, as named by the proof's `protocol`.
+    pub fn from_snarkjs(proof: &str, public: &str, verification_key: &str) -> Result<Self> {
+        let protocol = serde_json::from_str::<Value>(proof)?["protocol"].clone();
+        let artifacts = if protocol == FFLONK_PROTOCOL {
+            Self {
+                proof_system: ProofSystem::FfplonkBn254Bitvm,
+                proof: proof_from_snarkjs_fflonk(proof)?,
+                public_input: public_input_from_snarkjs(public)?,
+                vk: vk_from_snarkjs_fflonk(verification_key)?,
+            }
+        } else {
+            Self {
+                proof_system: ProofSystem::Groth16Bn254Bitvm,
+                proof: proof_from_snarkjs(proof)?,
+                public_input: public_input_from_snarkjs(public)?,
+                vk: vk_from_snarkjs(verification_key)?,
+            }
+        };
+        artifacts.validate()?;
+        Ok(artifacts)
+    }
+
+    pub fn from_snarkjs_files(
+        proof: impl AsRef<Path>,
+        public: impl AsRef<Path>,
+        verification_key: impl AsRef<Path>,
+    ) -> Result<Self> {
+        Self::from_snarkjs(
+            &read_to_string(proof.as_ref())?,
+            &read_to_string(public.as_ref())?,
+            &read_to_string(verification_key.as_ref())?,
+        )
+    }
+
+    /// Checks the encodings and that the VK expects as many public inputs as are given.
+    /// FFLONK artifacts are not checked, as the chain's FFLONK encoding is not known here.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let expected = match self.proof_system {
+            ProofSystem::Groth16Bn254Bitvm => validate_groth16_vk(&self.vk)?,
+            ProofSystem::FfplonkBn254Bitvm => return Ok(()),
+        };
+        validate_groth16_proof(&self.proof)?;
+        let actual = validate_public_input(&self.public_input)?;
+        if expected != actual {
+            return Err(ValidationError::PublicInputCountMismatch {
+                vk: expected,
+                public_input: actual,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn into_msg(
+        self,
+        creator: AccountId,
+        namespace: &str,
+        data_location: &str,
+    ) -> MsgSubmitProof {
+        MsgSubmitProof {
+            creator,
+            proof_system: self.proof_system.as_str_name().to_string(),
+            proof: self.proof,
+            public_input: self.public_input,
+            vk: self.vk,
+            namespace: namespace.to_string(),
+            data_location: data_location.to_string(),
+        }
+    }
+}
+
+/// Converts a snarkjs Groth16 `proof.json` (`pi_a`, `pi_b`, `pi_c`).
+pub fn proof_from_snarkjs(json: &str) -> Result<Vec<u8>> {
+    let json = parse_snarkjs(json, GROTH16_PROTOCOL)?;
+    let mut proof = Vec::with_capacity(GROTH16_PROOF_SIZE);
+    proof.extend(snarkjs_g1(&json["pi_a"], "pi_a")?);
+    proof.extend(snarkjs_g2(&json["pi_b"], "pi_b")?);
+    proof.extend(snarkjs_g1(&json["pi_c"], "pi_c")?);
+    validate_groth16_proof(&proof)?;
+    Ok(proof)
+}
+
+/// Converts a snarkjs Groth16 `verification_key.json`.
+pub fn vk_from_snarkjs(json: &str) -> Result<Vec<u8>> {
+    let json = parse_snarkjs(json, GROTH16_PROTOCOL)?;
+    let ic = json["IC"]
+        .as_array()
+        .ok_or(ErrorReport::msg("Missing IC in snarkjs verification key"))?;
+    if let Some(n_public) = json["nPublic"].as_u64() {
+        if n_public + 1 != ic.len() as u64 {
+            return Err(ErrorReport::msg(format!(
+                "snarkjs verification key has nPublic {} but {} IC points",
+                n_public,
+                ic.len()
+            )));
+        }
+    }
+    let mut vk = vec![];
+    vk.extend(snarkjs_g1(&json["vk_alpha_1"], "vk_alpha_1")?);
+    vk.extend(snarkjs_g2(&json["vk_beta_2"], "vk_beta_2")?);
+    vk.extend(snarkjs_g2(&json["vk_gamma_2"], "vk_gamma_2")?);
+    vk.extend(snarkjs_g2(&json["vk_delta_2"], "vk_delta_2")?);
+    vk.extend((ic.len() as u64).to_le_bytes());
+    for point in ic {
+        vk.extend(snarkjs_g1(point, "IC")?);
+    }
+    validate_groth16_vk(&vk)?;
+    Ok(vk)
+}
+
+/// Converts a snarkjs FFLONK `proof.json` (`polynomials` and `evaluations`) to the
+/// [`FFLONK_PROOF_SIZE`] layout, which is unchecked against the chain.
+pub fn proof_from_snarkjs_fflonk(json: &str) -> Result<Vec<u8>> {
+    let json = parse_snarkjs(json, FFLONK_PROTOCOL)?;
+    let mut proof = Vec::with_capacity(FFLONK_PROOF_SIZE);
+    for name in FFLONK_POLYNOMIALS {
+        proof.extend(snarkjs_g1(&json["polynomials"][name], name)?);
+    }
+    for name in FFLONK_EVALUATIONS {
+        proof.extend(snarkjs_fr(&json["evaluations"][name], name)?);
+    }
+    validate_fflonk_proof(&proof)?;
+    Ok(proof)
+}
+
+/// Converts a snarkjs FFLONK `verification_key.json` to the [`FFLONK_VK_SIZE`] layout, which
+/// is unchecked against the chain.
+pub fn vk_from_snarkjs_fflonk(json: &str) -> Result<Vec<u8>> {
+    let json = parse_snarkjs(json, FFLONK_PROTOCOL)?;
+    let mut vk = Vec::with_capacity(FFLONK_VK_SIZE);
+    for name in ["nPublic", "power"] {
+        let value = json[name].as_u64().ok_or(ErrorReport::msg(format!(
+            "Missing {} in snarkjs verification key",
+            name
+        )))?;
+        vk.extend(value.to_le_bytes());
+    }
+    for name in FFLONK_VK_ROOTS {
+        vk.extend(snarkjs_fr(&json[name], name)?);
+    }
+    vk.extend(snarkjs_g2(&json["X_2"], "X_2")?);
+    vk.extend(snarkjs_g1(&json["C0"], "C0")?);
+    validate_fflonk_vk(&vk)?;
+    Ok(vk)
+}
+
+/// Converts a snarkjs `public.json`, an array of decimal strings.
+pub fn public_input_from_snarkjs(json: &str) -> Result<Vec<u8>> {
+    let json: Value = serde_json::from_str(json)?;
+    let inputs = json
+        .as_array()
+        .ok_or(ErrorReport::msg("snarkjs public inputs must be an array"))?;
+    let mut public_input = (inputs.len() as u64).to_le_bytes().to_vec();
+    for input in inputs {
+        let input = input
+            .as_str()
+            .ok_or(ErrorReport::msg("snarkjs public input must be a string"))?;
+        public_input.extend(parse_decimal(input)?);
+    }
+    validate_public_input(&public_input)?;
+    Ok(public_input)
+}
+
+/// Converts a gnark BN254 Groth16 proof written by `WriteTo` (compressed) or `WriteRawTo`.
+/// Proofs with Pedersen commitments cannot be verified by the chain and are rejected.
+pub fn proof_from_gnark(bytes: &[u8]) -> Result<Vec<u8>> {
+    let flags = bytes.first().ok_or(ErrorReport::msg("Empty gnark proof"))? & FLAGS_MASK;
+    let compressed = match flags {
+        GNARK_COMPRESSED_SMALLEST | GNARK_COMPRESSED_LARGEST => true,
+        GNARK_UNCOMPRESSED => false,
+        _ => return Err(ErrorReport::msg("gnark proof point is at infinity")),
+    };
+    // A G2 point takes twice the space of a G1 point.
+    let g1_size = if compressed { 32 } else { 64 };
+    let points_size = 4 * g1_size;
+    if bytes.len() < points_size {
+        return Err(ValidationError::InvalidLength {
+            field: "proof",
+            expected: points_size,
+            actual: bytes.len(),
+        }
+        .into());
+    }
+    // gnark 0.9+ appends the commitments (u32 count, then points) and their proof of knowledge.
+    if let Some(count) = bytes.get(points_size..points_size + 4) {
+        if count != [0; 4] {
+            return Err(ErrorReport::msg(
+                "gnark proofs with commitments are not supported",
+            ));
+        }
+    }
+
+    let (a, rest) = bytes.split_at(g1_size);
+    let (b, rest) = rest.split_at(2 * g1_size);
+    let c = &rest[..g1_size];
+    let mut proof = Vec::with_capacity(GROTH16_PROOF_SIZE);
+    if compressed {
+        proof.extend(gnark_compressed_g1(a)?);
+        proof.extend(gnark_compressed_g2(b)?);
+        proof.extend(gnark_compressed_g1(c)?);
+    } else {
+        proof.extend(compress_g1(&be_fq(&a[..32])?, &be_fq(&a[32..])?));
+        proof.extend(compress_g2(
+            [&be_fq(&b[32..64])?, &be_fq(&b[..32])?],
+            [&be_fq(&b[96..])?, &be_fq(&b[64..96])?],
+        ));
+        proof.extend(compress_g1(&be_fq(&c[..32])?, &be_fq(&c[32..])?));
+    }
+    validate_groth16_proof(&proof)?;
+    Ok(proof)
+}
+
+/// Converts a gnark public witness written by `WriteTo`: `u32` public and secret counts, then
+/// a `u32` length and the big endian `Fr` elements, all big endian.
+pub fn public_input_from_gnark_witness(bytes: &[u8]) -> Result<Vec<u8>> {
+    let header = |offset: usize| {
+        bytes
+            .get(offset..offset + 4)
+            .map(|b| u32::from_be_bytes(b.try_into().expect("4 bytes")) as usize)
+            .ok_or(ErrorReport::msg("Truncated gnark witness"))
+    };
+    let (public, secret, len) = (header(0)?, header(4)?, header(8)?);
+    if secret != 0 || public != len {
+        return Err(ErrorReport::msg(
+            "gnark witness must be a public witness with no secret values",
+        ));
+    }
+    let elements = &bytes[12..];
+    if elements.len() != len * 32 {
+        return Err(ValidationError::InvalidLength {
+            field: "public_input",
+            expected: 12 + len * 32,
+            actual: bytes.len(),
+        }
+        .into());
+    }
+    let mut public_input = (len as u64).to_le_bytes().to_vec();
+    for element in elements.chunks(32) {
+        public_input.extend(element.iter().rev());
+    }
+    validate_public_input(&public_input)?;
+    Ok(public_input)
+}
+
+/// Converts an arkworks `Proof<Bn254>` serialized compressed (returned as is) or uncompressed.
+pub fn proof_from_arkworks(bytes: &[u8]) -> Result<Vec<u8>> {
+    let proof = match bytes.len() {
+        GROTH16_PROOF_SIZE => bytes.to_vec(),
+        len if len == 2 * GROTH16_PROOF_SIZE => {
+            // Uncompressed points are `x || y` with the flags in the last byte of `y`.
+            let g1 = |point: &[u8]| -> Result<[u8; 32]> {
+                Ok(compress_g1(&le_fq(&point[..32])?, &le_fq(&point[32..])?))
+            };
+            let (a, rest) = bytes.split_at(64);
+            let (b, c) = rest.split_at(128);
+            let mut proof = Vec::with_capacity(GROTH16_PROOF_SIZE);
+            proof.extend(g1(a)?);
+            proof.extend(compress_g2(
+                [&le_fq(&b[..32])?, &le_fq(&b[32..64])?],
+                [&le_fq(&b[64..96])?, &le_fq(&b[96..])?],
+            ));
+            proof.extend(g1(c)?);
+            proof
+        }
+        len => {
+            return Err(ValidationError::InvalidLength {
+                field: "proof",
+                expected: GROTH16_PROOF_SIZE,
+                actual: len,
+            }
+            .into())
+        }
+    };
+    validate_groth16_proof(&proof)?;
+    Ok(proof)
+}
+
+fn read(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path)
+        .map_err(|e| ErrorReport::msg(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+fn read_to_string(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|e| ErrorReport::msg(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+fn parse_snarkjs(json: &str, expected_protocol: &str) -> Result<Value> {
+    let json: Value = serde_json::from_str(json)?;
+    if let Some(protocol) = json["protocol"].as_str() {
+        if protocol != expected_protocol {
+            return Err(ErrorReport::msg(format!(
+                "Expected snarkjs protocol {}, got {}",
+                expected_protocol, protocol
+            )));
+        }
+    }
+    if let Some(curve) = json["curve"].as_str() {
+        if curve != "bn128" {
+            return Err(ErrorReport::msg(format!(
+                "Unsupported snarkjs curve {}",
+                curve
+            )));
+        }
+    }
+    Ok(json)
+}
+
+/// A snarkjs G1 point: `[x, y, "1"]` in decimal.
+fn snarkjs_g1(point: &Value, field: &str) -> Result<[u8; 32]> {
+    let coords = snarkjs_strings(point, field)?;
+    if coords.len() != 3 || coords[2] != "1" {
+        return Err(ErrorReport::msg(format!(
+            "{} is not an affine snarkjs G1 point",
+            field
+        )));
+    }
+    Ok(compress_g1(
+        &decimal_fq(coords[0])?,
+        &decimal_fq(coords[1])?,
+    ))
+}
+
+/// A snarkjs G2 point: `[[x.c0, x.c1], [y.c0, y.c1], ["1", "0"]]` in decimal.
+fn snarkjs_g2(point: &Value, field: &str) -> Result<[u8; 64]> {
+    let invalid = || ErrorReport::msg(format!("{} is not an affine snarkjs G2 point", field));
+    let coords = point.as_array().ok_or_else(invalid)?;
+    if coords.len() != 3 || snarkjs_strings(&coords[2], field)? != ["1", "0"] {
+        return Err(invalid());
+    }
+    let x = snarkjs_strings(&coords[0], field)?;
+    let y = snarkjs_strings(&coords[1], field)?;
+    if x.len() != 2 || y.len() != 2 {
+        return Err(invalid());
+    }
+    Ok(compress_g2(
+        [&decimal_fq(x[0])?, &decimal_fq(x[1])?],
+        [&decimal_fq(y[0])?, &decimal_fq(y[1])?],
+    ))
+}
+
+/// A snarkjs scalar: a decimal string.
+fn snarkjs_fr(value: &Value, field: &str) -> Result<[u8; 32]> {
+    let decimal = value.as_str().ok_or(ErrorReport::msg(format!(
+        "{} must be a decimal string",
+        field
+    )))?;
+    parse_decimal(decimal)
+}
+
+fn snarkjs_strings<'a>(value: &'a Value, field: &str) -> Result<Vec<&'a str>> {
+    value
+        .as_array()
+        .and_then(|items| items.iter().map(Value::as_str).collect())
+        .ok_or(ErrorReport::msg(format!(
+            "{} must be an array of decimal strings",
+            field
+        )))
+}
+
+fn gnark_compressed_g1(point: &[u8]) -> Result<[u8; 32]> {
+    let mut x = point.to_vec();
+    let largest = x[0] & FLAGS_MASK == GNARK_COMPRESSED_LARGEST;
+    x[0] &= !FLAGS_MASK;
+    let mut compressed = be_fq(&x)?;
+    if largest {
+        compressed[31] |= Y_IS_NEGATIVE;
+    }
+    Ok(compressed)
+}
+
+/// gnark stores `x.c1 || x.c0` and flags `x.c1`'s first byte.
+fn gnark_compressed_g2(point: &[u8]) -> Result<[u8; 64]> {
+    let c1 = gnark_compressed_g1(&point[..32])?;
+    let c0 = be_fq(&point[32..])?;
+    let mut compressed = [0; 64];
+    compressed[..32].copy_from_slice(&c0);
+    compressed[32..].copy_from_slice(&c1);
+    Ok(compressed)
+}
+
+fn compress_g1(x: &Fq, y: &Fq) -> [u8; 32] {
+    let mut compressed = *x;
+    if is_negative(y) {
+        compressed[31] |= Y_IS_NEGATIVE;
+    }
+    compressed
+}
+
+fn compress_g2(x: [&Fq; 2], y: [&Fq; 2]) -> [u8; 64] {
+    let negative = if is_zero(y[1]) {
+        is_negative(y[0])
+    } else {
+        is_negative(y[1])
+    };
+    let mut compressed = [0; 64];
+    compressed[..32].copy_from_slice(x[0]);
+    compressed[32..].copy_from_slice(x[1]);
+    if negative {
+        compressed[63] |= Y_IS_NEGATIVE;
+    }
+    compressed
+}
+
+/// Whether `y > p - y`, the arkworks and gnark "largest" `y`.
+fn is_negative(y: &Fq) -> bool {
+    if is_zero(y) {
+        return false;
+    }
+    let mut neg = [0; 32];
+    let mut borrow = 0;
+    for i in 0..32 {
+        let diff = FQ_MODULUS[i] as i16 - y[i] as i16 - borrow;
+        neg[i] = diff.rem_euclid(256) as u8;
+        borrow = (diff < 0) as i16;
+    }
+    less_than_le(&neg, y)
+}
+
+fn is_zero(element: &Fq) -> bool {
+    element.iter().all(|&b| b == 0)
+}
+
+/// A base field element from 32 little endian bytes, ignoring arkworks flag bits.
+fn le_fq(bytes: &[u8]) -> Result<Fq> {
+    let mut element: Fq = bytes.try_into().expect("field element is 32 bytes");
+    element[31] &= !FLAGS_MASK;
+    canonical_fq(element)
+}
+
+fn be_fq(bytes: &[u8]) -> Result<Fq> {
+    let mut element: Fq = bytes.try_into().expect("field element is 32 bytes");
+    element.reverse();
+    canonical_fq(element)
+}
+
+fn decimal_fq(decimal: &str) -> Result<Fq> {
+    canonical_fq(parse_decimal_256(decimal)?)
+}
+
+fn canonical_fq(element: Fq) -> Result<Fq> {
+    if less_than_le(&element, &FQ_MODULUS) {
+        Ok(element)
+    } else {
+        Err(ErrorReport::msg(
+            "Coordinate is not a canonical BN254 base field element",
+        ))
+    }
+}
+
+/// A scalar field element from its decimal representation, as 32 little endian bytes.
+fn parse_decimal(decimal: &str) -> Result<[u8; 32]> {
+    let element = parse_decimal_256(decimal)?;
+    if !less_than_le(&element, &FR_MODULUS) {
+        return Err(ErrorReport::msg(format!(
+            "{} is not a canonical BN254 scalar field element",
+            decimal
+        )));
+    }
+    Ok(element)
+}
+
+fn parse_decimal_256(decimal: &str) -> Result<[u8; 32]> {
+    if decimal.is_empty() || !decimal.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ErrorReport::msg(format!(
+            "{:?} is not a decimal integer",
+            decimal
+        )));
+    }
+    let mut value = [0u8; 32];
+    for digit in decimal.bytes() {
+        let mut carry = (digit - b'0') as u16;
+        for byte in value.iter_mut() {
+            let next = *byte as u16 * 10 + carry;
+            *byte = next as u8;
+            carry = next >> 8;
+        }
+        if carry != 0 {
+            return Err(ErrorReport::msg(format!(
+                "{} does not fit in 256 bits",
+                decimal
+            )));
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_decimal, proof_from_arkworks, proof_from_gnark, proof_from_snarkjs,
+        public_input_from_gnark_witness, public_input_from_snarkjs, ProofArtifacts,
+        GNARK_COMPRESSED_LARGEST, GNARK_COMPRESSED_SMALLEST, Y_IS_NEGATIVE,
+    };
+    use crate::{types::ProofSystem, validation::FLAGS_MASK};
+    use serde_json::Value;
+
+    const TEST_DATA: &str = "test-data";
+    const FFLONK_TEST_DATA: &str = "test-data/fflonk";
+
+    fn test_artifacts() -> ProofArtifacts {
+        ProofArtifacts::load(std::env::current_dir().unwrap().join(TEST_DATA)).unwrap()
+    }
+
+    fn fflonk_json(file: &str) -> String {
+        let dir = std::env::current_dir().unwrap().join(FFLONK_TEST_DATA);
+        std::fs::read_to_string(dir.join(file)).unwrap()
+    }
+
+    /// Re-encodes an arkworks compressed G1 point the way gnark's `WriteTo` does. This mirrors
+    /// the converter's own flag mapping; `conversions::converts_gnark_compressed_from_coordinates` checks
+    /// the encoding against the curve points instead.
+    fn gnark_g1(point: &[u8]) -> Vec<u8> {
+        let mut x = point.to_vec();
+        let flag = if x[31] & Y_IS_NEGATIVE != 0 {
+            GNARK_COMPRESSED_LARGEST
+        } else {
+            GNARK_COMPRESSED_SMALLEST
+        };
+        x[31] &= !FLAGS_MASK;
+        x.reverse();
+        x[0] |= flag;
+        x
+    }
+
+    #[test]
+    fn load_write_and_validate() {
+        let artifacts = test_artifacts();
+        assert_eq!(artifacts.validate(), Ok(()));
+
+        let dir = std::env::temp_dir().join(format!("fiamma-artifacts-{}", std::process::id()));
+        artifacts.write(&dir).unwrap();
+        assert_eq!(ProofArtifacts::load(&dir).unwrap(), artifacts);
+
+        std::fs::write(dir.join("proof.bitvm"), &artifacts.proof[..100]).unwrap();
+        assert!(ProofArtifacts::load(&dir).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn converts_snarkjs_fflonk() {
+        let dir = std::env::current_dir().unwrap().join(FFLONK_TEST_DATA);
+        let artifacts = ProofArtifacts::load_as(&dir, ProofSystem::FfplonkBn254Bitvm).unwrap();
+        assert!(ProofArtifacts::load(&dir).is_err());
+        let converted = ProofArtifacts::from_snarkjs_files(
+            dir.join("proof.json"),
+            dir.join("public.json"),
+            dir.join("verification_key.json"),
+        )
+        .unwrap();
+        assert_eq!(converted, artifacts);
+
+        let (proof, public) = (fflonk_json("proof.json"), fflonk_json("public.json"));
+        assert!(proof_from_snarkjs(&proof).is_err());
+
+        // FFLONK bytes are left to the chain, whatever their shape.
+        let truncated = ProofArtifacts {
+            proof: artifacts.proof[..100].to_vec(),
+            ..artifacts
+        };
+        assert_eq!(truncated.validate(), Ok(()));
+
+        let mut proof: Value = serde_json::from_str(&proof).unwrap();
+        proof["evaluations"]["inv"] =
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617".into();
+        let vk = fflonk_json("verification_key.json");
+        assert!(ProofArtifacts::from_snarkjs(&proof.to_string(), &public, &vk).is_err());
+    }
+
+    #[test]
+    fn converts_gnark_compressed_proof() {
+        let proof = test_artifacts().proof;
+        let mut gnark = gnark_g1(&proof[..32]);
+        let mut b = gnark_g1(&proof[64..96]);
+        let mut b_c0 = proof[32..64].to_vec();
+        b_c0.reverse();
+        b.extend(b_c0);
+        gnark.extend(b);
+        gnark.extend(gnark_g1(&proof[96..]));
+        assert_eq!(proof_from_gnark(&gnark).unwrap(), proof);
+
+        // gnark 0.9+ appends an empty commitment list and its proof of knowledge.
+        gnark.extend([0; 4]);
+        gnark.extend([0x40; 32]);
+        assert_eq!(proof_from_gnark(&gnark).unwrap(), proof);
+        gnark[128..132].copy_from_slice(&[0, 0, 0, 1]);
+        assert!(proof_from_gnark(&gnark).is_err());
+    }
+
+    #[test]
+    fn converts_public_inputs() {
+        let public_input = test_artifacts().public_input;
+        let elements: Vec<&[u8]> = public_input[8..].chunks(32).collect();
+
+        let mut witness = vec![];
+        for header in [elements.len(), 0, elements.len()] {
+            witness.extend((header as u32).to_be_bytes());
+        }
+        for element in &elements {
+            witness.extend(element.iter().rev());
+        }
+        assert_eq!(
+            public_input_from_gnark_witness(&witness).unwrap(),
+            public_input
+        );
+
+        assert_eq!(
+            public_input_from_snarkjs(r#"["0", "258"]"#).unwrap()[8..],
+            [[0; 32].as_slice(), &[2, 1], &[0; 30]].concat()
+        );
+        // r itself is not a canonical scalar.
+        let r = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        assert!(parse_decimal(r).is_err());
+        assert!(parse_decimal("-1").is_err());
+    }
+
+    #[test]
+    fn arkworks_compressed_passes_through() {
+        let proof = test_artifacts().proof;
+        assert_eq!(proof_from_arkworks(&proof).unwrap(), proof);
+        assert!(proof_from_arkworks(&proof[1..]).is_err());
+    }
+
+    #[cfg(feature = "local-verifier")]
+    mod conversions {
+        use super::test_artifacts;
+        use crate::{
+            local_verifier::verify_groth16,
+            proof_artifacts::{proof_from_arkworks, proof_from_gnark, ProofArtifacts},
+            types::ProofSystem,
+        };
+        use ark_bn254::{Bn254, Fq, G1Affine, G2Affine};
+        use ark_groth16::{Proof, VerifyingKey};
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+        use serde_json::{json, Value};
+
+        fn g1_json(point: &G1Affine) -> Value {
+            json!([point.x.to_string(), point.y.to_string(), "1"])
+        }
+
+        fn g2_json(point: &G2Affine) -> Value {
+            json!([
+                [point.x.c0.to_string(), point.x.c1.to_string()],
+                [point.y.c0.to_string(), point.y.c1.to_string()],
+                ["1", "0"]
+            ])
+        }
+
+        fn be(element: &Fq) -> Vec<u8> {
+            let mut bytes = vec![];
+            element.serialize_uncompressed(&mut bytes).unwrap();
+            bytes.reverse();
+            bytes
+        }
+
+        #[test]
+        fn converts_snarkjs() {
+            let artifacts = test_artifacts();
+            let proof = Proof::<Bn254>::deserialize_compressed(&*artifacts.proof).unwrap();
+            let vk = VerifyingKey::<Bn254>::deserialize_compressed(&*artifacts.vk).unwrap();
+            let public = Vec::<ark_bn254::Fr>::deserialize_compressed(&*artifacts.public_input)
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            let proof_json = json!({
+                "pi_a": g1_json(&proof.a),
+                "pi_b": g2_json(&proof.b),
+                "pi_c": g1_json(&proof.c),
+                "protocol": "groth16",
+                "curve": "bn128",
+            });
+            let vk_json = json!({
+                "protocol": "groth16",
+                "curve": "bn128",
+                "nPublic": public.len(),
+                "vk_alpha_1": g1_json(&vk.alpha_g1),
+                "vk_beta_2": g2_json(&vk.beta_g2),
+                "vk_gamma_2": g2_json(&vk.gamma_g2),
+                "vk_delta_2": g2_json(&vk.delta_g2),
+                "IC": vk.gamma_abc_g1.iter().map(g1_json).collect::<Vec<_>>(),
+            });
+            let converted = ProofArtifacts::from_snarkjs(
+                &proof_json.to_string(),
+                &json!(public).to_string(),
+                &vk_json.to_string(),
+            )
+            .unwrap();
+            assert_eq!(converted, artifacts);
+            assert!(
+                verify_groth16(&converted.proof, &converted.public_input, &converted.vk).unwrap()
+            );
+        }
+
+        #[test]
+        fn fflonk_points_are_on_curve() {
+            let dir = std::env::current_dir()
+                .unwrap()
+                .join(super::FFLONK_TEST_DATA);
+            let artifacts = ProofArtifacts::load_as(dir, ProofSystem::FfplonkBn254Bitvm).unwrap();
+            // arkworks checks that deserialized points are on the curve and in the subgroup.
+            for point in artifacts.proof[..128].chunks(32) {
+                assert!(G1Affine::deserialize_compressed(point).is_ok());
+            }
+            assert!(G2Affine::deserialize_compressed(&artifacts.vk[240..304]).is_ok());
+            assert!(G1Affine::deserialize_compressed(&artifacts.vk[304..]).is_ok());
+        }
+
+        #[test]
+        fn converts_uncompressed_proofs() {
+            let artifacts = test_artifacts();
+            let proof = Proof::<Bn254>::deserialize_compressed(&*artifacts.proof).unwrap();
+
+            let mut arkworks = vec![];
+            proof.serialize_uncompressed(&mut arkworks).unwrap();
+            assert_eq!(proof_from_arkworks(&arkworks).unwrap(), artifacts.proof);
+
+            let (a, b, c) = (&proof.a, &proof.b, &proof.c);
+            let gnark: Vec<u8> = [&a.x, &a.y, &b.x.c1, &b.x.c0, &b.y.c1, &b.y.c0, &c.x, &c.y]
+                .into_iter()
+                .flat_map(be)
+                .collect();
+            assert_eq!(proof_from_gnark(&gnark).unwrap(), artifacts.proof);
+        }
+
+        /// Builds gnark's `WriteTo` encoding from the points' coordinates, picking the
+        /// compression flag with arkworks' own field ordering rather than the converter's flag
+        /// mapping. `converts_uncompressed_proofs` covers `WriteRawTo`. No fixture written by
+        /// gnark itself is checked in.
+        #[test]
+        fn converts_gnark_compressed_from_coordinates() {
+            let artifacts = test_artifacts();
+            let proof = Proof::<Bn254>::deserialize_compressed(&*artifacts.proof).unwrap();
+
+            // gnark's compressed flags: 0b10 for the lexicographically smallest y, 0b11 for the
+            // largest; G2 coordinates compare A1 before A0, as arkworks orders Fq2.
+            let g1 = |point: &G1Affine| {
+                let mut bytes = be(&point.x);
+                bytes[0] |= if point.y > -point.y {
+                    0b11 << 6
+                } else {
+                    0b10 << 6
+                };
+                bytes
+            };
+            let g2 = |point: &G2Affine| {
+                let mut bytes = [be(&point.x.c1), be(&point.x.c0)].concat();
+                bytes[0] |= if point.y > -point.y {
+                    0b11 << 6
+                } else {
+                    0b10 << 6
+                };
+                bytes
+            };
+            let write_to = [g1(&proof.a), g2(&proof.b), g1(&proof.c)].concat();
+            assert_eq!(proof_from_gnark(&write_to).unwrap(), artifacts.proof);
+
+            // Flipping a compressed flag selects the other y and must not round-trip.
+            let mut flipped = write_to.clone();
+            flipped[0] ^= 1 << 6;
+            assert_ne!(proof_from_gnark(&flipped).ok(), Some(artifacts.proof));
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use cosmrs::Result;
+use http_body::{Body as _, Full};
+use std::{
+    collections::hash_map::RandomState,
+    error::Error,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tonic::{
+    body::BoxBody,
+    codegen::{http, Bytes, StdError},
+    transport::{Channel, Endpoint},
+    Code,
+};
+use tower::{Layer, Service, ServiceBuilder};
+
+/// gRPC methods that must not be sent twice. A failed call to one of these is only retried
+/// if the connection was refused, i.e. the request never reached the node.
+pub const NON_IDEMPOTENT_METHODS: &[&str] = &["/cosmos.tx.v1beta1.Service/BroadcastTx"];
+
+/// When and how often failed gRPC calls are retried.
+///
+/// Transport errors and responses whose `grpc-status` is in `retryable_codes` are retried
+/// with exponential backoff, up to `max_attempts` calls in total.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Each backoff is scaled by a random factor in `1 - jitter ..= 1 + jitter`.
+    pub jitter: f64,
+    pub retryable_codes: Vec<Code>,
+    pub non_idempotent_methods: Vec<String>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.2,
+            retryable_codes: vec![Code::Unavailable],
+            non_idempotent_methods: NON_IDEMPOTENT_METHODS
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_retryable_codes(mut self, retryable_codes: Vec<Code>) -> Self {
+        self.retryable_codes = retryable_codes;
+        self
+    }
+
+    pub fn with_non_idempotent_method(mut self, method: &str) -> Self {
+        self.non_idempotent_methods.push(method.to_string());
+        self
+    }
+
+    pub fn is_idempotent(&self, method: &str) -> bool {
+        !self.non_idempotent_methods.iter().any(|m| m == method)
+    }
+
+    /// Backoff before retry number `retry` (starting at 1), jitter included.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let backoff = backoff.min(self.max_backoff.as_secs_f64());
+        let factor = 1.0 + self.jitter * (2.0 * random_unit() - 1.0);
+        Duration::from_secs_f64((backoff * factor).max(0.0))
+    }
+
+    pub(crate) fn should_retry_error(&self, method: &str, error: &StdError) -> bool {
+        self.is_idempotent(method) || is_connection_refused(error.as_ref())
+    }
+
+    pub(crate) fn should_retry_status(&self, method: &str, code: Code) -> bool {
+        self.is_idempotent(method) && self.retryable_codes.contains(&code)
+    }
+}
+
+/// A random number in `0.0..1.0`, good enough for jitter.
+fn random_unit() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn is_connection_refused(error: &(dyn Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(io_error) = error.downcast_ref::<io::Error>() {
+            if io_error.kind() == io::ErrorKind::ConnectionRefused {
+                return true;
+            }
+        }
+        source = error.source();
+    }
+    false
+}
+
+/// The status of a trailers-only response. Statuses sent in trailers after a body are not
+/// seen here, but nodes report `Unavailable` and similar failures as trailers-only.
+pub(crate) fn header_status<B>(response: &http::Response<B>) -> Option<Code> {
+    let status = response.headers().get("grpc-status")?.to_str().ok()?;
+    Some(Code::from_i32(status.parse().ok()?))
+}
+
+#[derive(Clone, Debug)]
+pub struct RetryLayer {
+    policy: RetryPolicy,
+}
+
+impl RetryLayer {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for RetryLayer {
+    type Service = RetryService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RetryService {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+/// Retries gRPC calls according to a [`RetryPolicy`]. The request body is buffered so it
+/// can be resent.
+#[derive(Clone, Debug)]
+pub struct RetryService<S> {
+    inner: S,
+    policy: RetryPolicy,
+}
+
+impl<S, B> Service<http::Request<BoxBody>> for RetryService<S>
+where
+    S: Service<http::Request<BoxBody>, Response = http::Response<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<StdError>,
+    B: Send + 'static,
+{
+    type Response = http::Response<B>;
+    type Error = StdError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        // The ready service handles the first attempt; later attempts use clones.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let policy = self.policy.clone();
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = collect(body).await?;
+            let method = parts.uri.path().to_string();
+
+            let mut attempt = 1;
+            loop {
+                let request = rebuild(&parts, body.clone());
+                let result = inner.call(request).await.map_err(Into::into);
+                let retry = match &result {
+                    Ok(response) => header_status(response)
+                        .is_some_and(|code| policy.should_retry_status(&method, code)),
+                    Err(error) => policy.should_retry_error(&method, error),
+                };
+                if !retry || attempt >= policy.max_attempts {
+                    return result;
+                }
+
+                tokio::time::sleep(policy.backoff(attempt)).await;
+                attempt += 1;
+                wait_ready(&mut inner).await?;
+            }
+        })
+    }
+}
+
+pub(crate) async fn wait_ready<S, R>(service: &mut S) -> Result<(), StdError>
+where
+    S: Service<R>,
+    S::Error: Into<StdError>,
+{
+    std::future::poll_fn(|cx| service.poll_ready(cx))
+        .await
+        .map_err(Into::into)
+}
+
+pub(crate) async fn collect(mut body: BoxBody) -> Result<Bytes, StdError> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk?);
+    }
+    Ok(bytes.into())
+}
+
+pub(crate) fn rebuild(parts: &http::request::Parts, body: Bytes) -> http::Request<BoxBody> {
+    let body = Full::new(body)
+        .map_err(|never| match never {})
+        .boxed_unsync();
+    let mut request = http::Request::new(body);
+    *request.method_mut() = parts.method.clone();
+    *request.uri_mut() = parts.uri.clone();
+    *request.version_mut() = parts.version;
+    *request.headers_mut() = parts.headers.clone();
+    request
+}
+
+/// A lazily connected channel to `rpc` with `policy` applied. Connecting lazily means a
+/// node that is down surfaces as a retryable call error rather than failing up front.
+pub(crate) fn channel(rpc: &str, policy: &RetryPolicy) -> Result<RetryService<Channel>> {
+    let channel = Endpoint::from_shared(rpc.to_string())?.connect_lazy();
+    Ok(ServiceBuilder::new()
+        .layer(RetryLayer::new(policy.clone()))
+        .service(channel))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RetryLayer, RetryPolicy};
+    use std::{
+        future::{ready, Ready},
+        io,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+        time::Duration,
+    };
+    use tonic::{
+        body::{empty_body, BoxBody},
+        codegen::{http, StdError},
+        Code,
+    };
+    use tower::{Layer, Service, ServiceExt};
+
+    const QUERY: &str = "/fiamma.zkpverify.Query/PendingProof";
+    const BROADCAST: &str = "/cosmos.tx.v1beta1.Service/BroadcastTx";
+
+    /// Fails with `error` for the first `failures` calls, then succeeds.
+    #[derive(Clone)]
+    struct Flaky {
+        calls: Arc<AtomicU32>,
+        failures: u32,
+        error: fn() -> Result<http::Response<BoxBody>, StdError>,
+    }
+
+    impl Service<http::Request<BoxBody>> for Flaky {
+        type Response = http::Response<BoxBody>;
+        type Error = StdError;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<BoxBody>) -> Self::Future {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call <= self.failures {
+                ready((self.error)())
+            } else {
+                ready(Ok(http::Response::new(empty_body())))
+            }
+        }
+    }
+
+    fn unavailable() -> Result<http::Response<BoxBody>, StdError> {
+        let mut response = http::Response::new(empty_body());
+        response
+            .headers_mut()
+            .insert("grpc-status", (Code::Unavailable as i32).into());
+        Ok(response)
+    }
+
+    fn connection_refused() -> Result<http::Response<BoxBody>, StdError> {
+        Err(io::Error::from(io::ErrorKind::ConnectionRefused).into())
+    }
+
+    fn connection_reset() -> Result<http::Response<BoxBody>, StdError> {
+        Err(io::Error::from(io::ErrorKind::ConnectionReset).into())
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::default()
+            .with_max_attempts(3)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(1))
+    }
+
+    async fn calls(
+        method: &str,
+        failures: u32,
+        error: fn() -> Result<http::Response<BoxBody>, StdError>,
+    ) -> (u32, bool) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let service = RetryLayer::new(policy()).layer(Flaky {
+            calls: calls.clone(),
+            failures,
+            error,
+        });
+        let request = http::Request::builder()
+            .uri(format!("http://localhost{}", method))
+            .body(empty_body())
+            .unwrap();
+        let result = service.oneshot(request).await;
+        let ok = result.is_ok_and(|response| response.headers().get("grpc-status").is_none());
+        (calls.load(Ordering::SeqCst), ok)
+    }
+
+    #[tokio::test]
+    async fn retries_unavailable_queries() {
+        assert_eq!(calls(QUERY, 2, unavailable).await, (3, true));
+        assert_eq!(calls(QUERY, 5, unavailable).await, (3, false));
+        assert_eq!(calls(QUERY, 1, connection_reset).await, (2, true));
+    }
+
+    #[tokio::test]
+    async fn broadcasts_are_not_resent() {
+        assert_eq!(calls(BROADCAST, 1, unavailable).await, (1, false));
+        assert_eq!(calls(BROADCAST, 1, connection_reset).await, (1, false));
+        assert_eq!(calls(BROADCAST, 1, connection_refused).await, (2, true));
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(300))
+            .with_jitter(0.0);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+
+        let jittered = policy.with_jitter(0.5).backoff(1);
+        assert!(jittered >= Duration::from_millis(50) && jittered <= Duration::from_millis(150));
+    }
+}
@@ -0,0 +1,283 @@
+//! `serde(with = ...)` adapters giving the JSON forms of the `serde` feature: hex bytes,
+//! proto enum names from `as_str_name`, RFC 3339 times and `Any` as a type URL plus hex value.
+
+use crate::types::{DataLocation, ProofSystem, VerificationStatus};
+use cosmrs::{tendermint::Time, Any};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::time::SystemTime;
+
+pub(crate) mod hex_bytes {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let hex_str = String::deserialize(deserializer)?;
+        hex::decode(hex_str.trim_start_matches("0x")).map_err(de::Error::custom)
+    }
+}
+
+pub(crate) mod hex_bytes_vec {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        items: &[Vec<u8>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(items.iter().map(hex::encode))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|item| hex::decode(item.trim_start_matches("0x")).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+pub(crate) mod option_rfc3339 {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        time: &Option<SystemTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => {
+                let since_epoch = time
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map_err(ser::Error::custom)?;
+                let time = Time::from_unix_timestamp(
+                    since_epoch.as_secs() as i64,
+                    since_epoch.subsec_nanos(),
+                )
+                .map_err(ser::Error::custom)?;
+                serializer.serialize_some(&time.to_rfc3339())
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<SystemTime>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|time| {
+                Time::parse_from_rfc3339(&time)
+                    .and_then(|time| time.duration_since(Time::unix_epoch()))
+                    .map(|since_epoch| SystemTime::UNIX_EPOCH + since_epoch)
+                    .map_err(de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonAny {
+    type_url: String,
+    #[serde(with = "hex_bytes")]
+    value: Vec<u8>,
+}
+
+pub(crate) mod option_any {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        any: &Option<Any>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        any.as_ref()
+            .map(|any| JsonAny {
+                type_url: any.type_url.clone(),
+                value: any.value.clone(),
+            })
+            .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Any>, D::Error> {
+        Ok(
+            Option::<JsonAny>::deserialize(deserializer)?.map(|any| Any {
+                type_url: any.type_url,
+                value: any.value,
+            }),
+        )
+    }
+}
+
+/// Serializes a proto enum by its `as_str_name`, both as a type and as the `i32` field prost
+/// stores it in.
+macro_rules! proto_enum_name {
+    ($module:ident, $enum:ty) => {
+        impl Serialize for $enum {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str_name())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $enum {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                <$enum>::from_str_name(&name).ok_or_else(|| {
+                    de::Error::custom(format!("unknown {} {}", stringify!($enum), name))
+                })
+            }
+        }
+
+        pub(crate) mod $module {
+            use super::*;
+
+            pub(crate) fn serialize<S: Serializer>(
+                value: &i32,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                <$enum>::try_from(*value)
+                    .map_err(|_| {
+                        ser::Error::custom(format!("unknown {} {}", stringify!($enum), value))
+                    })?
+                    .serialize(serializer)
+            }
+
+            pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<i32, D::Error> {
+                Ok(<$enum>::deserialize(deserializer)? as i32)
+            }
+        }
+    };
+}
+
+proto_enum_name!(proof_system, ProofSystem);
+proto_enum_name!(data_location, DataLocation);
+proto_enum_name!(verification_status, VerificationStatus);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        chain_query::BlockInfo,
+        types::{
+            DaSubmissionResult, DataLocation, MsgSubmitProof, MsgUpdateDASubmissionResults,
+            ProofData, ProofSystem, VerificationStatus, VerifyResult,
+        },
+        wallet::{AccountInfo, AccountKind, Wallet},
+    };
+    use cosmrs::Any;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::{json, Value};
+    use std::{fmt::Debug, time::Duration, time::SystemTime};
+
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) -> Value {
+        let json = serde_json::to_value(value).unwrap();
+        assert_eq!(&serde_json::from_value::<T>(json.clone()).unwrap(), value);
+        json
+    }
+
+    #[test]
+    fn msgs_round_trip() {
+        let creator = Wallet::new(PRIVATE_KEY).account_id;
+        let msg = MsgSubmitProof {
+            creator: creator.clone(),
+            proof_system: "GROTH16_BN254_BITVM".to_string(),
+            proof: vec![0xde, 0xad],
+            public_input: vec![],
+            vk: vec![0x01],
+            namespace: "test-namespace".to_string(),
+            data_location: "FIAMMA".to_string(),
+        };
+        assert_eq!(
+            round_trip(&msg),
+            json!({
+                "creator": creator.to_string(),
+                "proof_system": "GROTH16_BN254_BITVM",
+                "proof": "dead",
+                "public_input": "",
+                "vk": "01",
+                "namespace": "test-namespace",
+                "data_location": "FIAMMA",
+            })
+        );
+
+        let msg = MsgUpdateDASubmissionResults {
+            creator,
+            da_submission_results: vec![DaSubmissionResult {
+                proof_id: "proof".to_string(),
+                data_location: DataLocation::Nubit as i32,
+                block_height: 7,
+                ..Default::default()
+            }],
+        };
+        let json = round_trip(&msg);
+        assert_eq!(json["da_submission_results"][0]["data_location"], "NUBIT");
+    }
+
+    #[test]
+    fn query_results_round_trip() {
+        let verify_result = VerifyResult {
+            proof_id: "proof".to_string(),
+            proof_system: ProofSystem::FfplonkBn254Bitvm as i32,
+            result: true,
+            status: VerificationStatus::HardFinality as i32,
+            community_verification_count: 3,
+            namespace: "test-namespace".to_string(),
+        };
+        let json = round_trip(&verify_result);
+        assert_eq!(json["proof_system"], "FFPLONK_BN254_BITVM");
+        assert_eq!(json["status"], "HARD_FINALITY");
+
+        let proof_data = ProofData {
+            proof: vec![0xff],
+            ..Default::default()
+        };
+        assert_eq!(round_trip(&proof_data)["proof"], "ff");
+
+        let mut json = serde_json::to_value(&verify_result).unwrap();
+        json["status"] = json!("FINAL");
+        assert!(serde_json::from_value::<VerifyResult>(json).is_err());
+        let verify_result = VerifyResult {
+            status: 9,
+            ..verify_result
+        };
+        assert!(serde_json::to_value(&verify_result).is_err());
+    }
+
+    #[test]
+    fn chain_results_round_trip() {
+        let block = BlockInfo {
+            height: 42,
+            time: Some(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 5)),
+            hash: "AB".repeat(32),
+            chain_id: "fiamma-testnet-1".to_string(),
+            proposer_address: "0102".to_string(),
+            txs: vec![vec![1, 2, 3]],
+        };
+        let json = round_trip(&block);
+        assert_eq!(json["time"], "2023-11-14T22:13:20.000000005Z");
+        assert_eq!(json["txs"], json!(["010203"]));
+
+        let account = AccountInfo {
+            address: Wallet::new(PRIVATE_KEY).account_id.to_string(),
+            pub_key: Some(Any {
+                type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                value: vec![0x0a],
+            }),
+            account_number: 1,
+            sequence: 2,
+            kind: AccountKind::Module {
+                name: "bank".to_string(),
+                permissions: vec!["burner".to_string()],
+            },
+        };
+        let json = round_trip(&account);
+        assert_eq!(json["pub_key"]["value"], "0a");
+        assert_eq!(json["kind"]["type"], "module");
+    }
+}
@@ -0,0 +1,300 @@
+//! An [`IndexStore`] backed by an embedded SQLite database.
+
+use crate::indexer::{
+    DaSubmissionRecord, IndexStore, IndexedBlock, ProofRecord, VerificationRecord,
+};
+use cosmrs::{ErrorReport, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS checkpoint (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    height INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS proofs (
+    proof_id TEXT PRIMARY KEY,
+    height INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL,
+    creator TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+    proof_system TEXT NOT NULL,
+    data_location TEXT NOT NULL,
+    proof BLOB NOT NULL,
+    public_input BLOB NOT NULL,
+    vk BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS proofs_namespace ON proofs (namespace);
+CREATE TABLE IF NOT EXISTS verifications (
+    proof_id TEXT NOT NULL,
+    height INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL,
+    verifier TEXT NOT NULL,
+    verify_result INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS verifications_proof_id ON verifications (proof_id);
+CREATE TABLE IF NOT EXISTS da_submissions (
+    proof_id TEXT NOT NULL,
+    height INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL,
+    submitter TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+    data_location TEXT NOT NULL,
+    da_block_hash TEXT NOT NULL,
+    da_block_height INTEGER NOT NULL,
+    da_tx_hash TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS da_submissions_proof_id ON da_submissions (proof_id);
+";
+
+const PROOF_COLUMNS: &str = "proof_id, height, tx_hash, creator, namespace, proof_system, \
+                             data_location, proof, public_input, vk";
+
+/// Persists indexed blocks and the checkpoint in SQLite, writing each block in one transaction.
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl SqliteStore {
+    /// Opens or creates the index database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+}
+
+fn to_i64(n: u64) -> Result<i64> {
+    i64::try_from(n).map_err(|_| ErrorReport::msg(format!("{} does not fit in SQLite", n)))
+}
+
+fn proof_record(row: &Row) -> rusqlite::Result<ProofRecord> {
+    Ok(ProofRecord {
+        proof_id: row.get(0)?,
+        height: row.get::<_, i64>(1)? as u64,
+        tx_hash: row.get(2)?,
+        creator: row.get(3)?,
+        namespace: row.get(4)?,
+        proof_system: row.get(5)?,
+        data_location: row.get(6)?,
+        proof: row.get(7)?,
+        public_input: row.get(8)?,
+        vk: row.get(9)?,
+    })
+}
+
+impl IndexStore for SqliteStore {
+    fn checkpoint(&self) -> Result<Option<u64>> {
+        let height = self
+            .connection
+            .query_row("SELECT height FROM checkpoint WHERE id = 0", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .optional()?;
+        Ok(height.map(|height| height as u64))
+    }
+
+    fn write_block(&mut self, block: &IndexedBlock) -> Result<()> {
+        let height = to_i64(block.height)?;
+        let tx = self.connection.transaction()?;
+        for proof in &block.proofs {
+            tx.execute(
+                &format!(
+                    "INSERT OR REPLACE INTO proofs ({}) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    PROOF_COLUMNS
+                ),
+                params![
+                    proof.proof_id,
+                    height,
+                    proof.tx_hash,
+                    proof.creator,
+                    proof.namespace,
+                    proof.proof_system,
+                    proof.data_location,
+                    proof.proof,
+                    proof.public_input,
+                    proof.vk,
+                ],
+            )?;
+        }
+        for verification in &block.verifications {
+            tx.execute(
+                "INSERT INTO verifications (proof_id, height, tx_hash, verifier, verify_result) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    verification.proof_id,
+                    height,
+                    verification.tx_hash,
+                    verification.verifier,
+                    verification.verify_result,
+                ],
+            )?;
+        }
+        for submission in &block.da_submissions {
+            tx.execute(
+                "INSERT INTO da_submissions (proof_id, height, tx_hash, submitter, namespace, \
+                 data_location, da_block_hash, da_block_height, da_tx_hash) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    submission.proof_id,
+                    height,
+                    submission.tx_hash,
+                    submission.submitter,
+                    submission.namespace,
+                    submission.data_location,
+                    submission.da_block_hash,
+                    to_i64(submission.da_block_height)?,
+                    submission.da_tx_hash,
+                ],
+            )?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO checkpoint (id, height) VALUES (0, ?1)",
+            params![height],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn proof(&self, proof_id: &str) -> Result<Option<ProofRecord>> {
+        Ok(self
+            .connection
+            .query_row(
+                &format!("SELECT {} FROM proofs WHERE proof_id = ?1", PROOF_COLUMNS),
+                params![proof_id],
+                proof_record,
+            )
+            .optional()?)
+    }
+
+    fn proofs_by_namespace(&self, namespace: &str) -> Result<Vec<ProofRecord>> {
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT {} FROM proofs WHERE namespace = ?1 ORDER BY height, proof_id",
+            PROOF_COLUMNS
+        ))?;
+        let proofs = statement
+            .query_map(params![namespace], proof_record)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(proofs)
+    }
+
+    fn verifications(&self, proof_id: &str) -> Result<Vec<VerificationRecord>> {
+        let mut statement = self.connection.prepare(
+            "SELECT proof_id, height, tx_hash, verifier, verify_result FROM verifications \
+             WHERE proof_id = ?1 ORDER BY rowid",
+        )?;
+        let verifications = statement
+            .query_map(params![proof_id], |row| {
+                Ok(VerificationRecord {
+                    proof_id: row.get(0)?,
+                    height: row.get::<_, i64>(1)? as u64,
+                    tx_hash: row.get(2)?,
+                    verifier: row.get(3)?,
+                    verify_result: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(verifications)
+    }
+
+    fn da_submissions(&self, proof_id: &str) -> Result<Vec<DaSubmissionRecord>> {
+        let mut statement = self.connection.prepare(
+            "SELECT proof_id, height, tx_hash, submitter, namespace, data_location, \
+             da_block_hash, da_block_height, da_tx_hash FROM da_submissions \
+             WHERE proof_id = ?1 ORDER BY rowid",
+        )?;
+        let submissions = statement
+            .query_map(params![proof_id], |row| {
+                Ok(DaSubmissionRecord {
+                    proof_id: row.get(0)?,
+                    height: row.get::<_, i64>(1)? as u64,
+                    tx_hash: row.get(2)?,
+                    submitter: row.get(3)?,
+                    namespace: row.get(4)?,
+                    data_location: row.get(5)?,
+                    da_block_hash: row.get(6)?,
+                    da_block_height: row.get::<_, i64>(7)? as u64,
+                    da_tx_hash: row.get(8)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(submissions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqliteStore;
+    use crate::indexer::{
+        DaSubmissionRecord, IndexStore, IndexedBlock, ProofRecord, VerificationRecord,
+    };
+
+    fn block(height: u64) -> IndexedBlock {
+        IndexedBlock {
+            height,
+            time: None,
+            proofs: vec![ProofRecord {
+                proof_id: "proof".to_string(),
+                height,
+                tx_hash: "tx".to_string(),
+                creator: "creator".to_string(),
+                namespace: "test-namespace".to_string(),
+                proof_system: "GROTH16_BN254_BITVM".to_string(),
+                data_location: "FIAMMA".to_string(),
+                proof: vec![1],
+                public_input: vec![2],
+                vk: vec![3],
+            }],
+            verifications: vec![VerificationRecord {
+                proof_id: "proof".to_string(),
+                height,
+                tx_hash: "tx".to_string(),
+                verifier: "verifier".to_string(),
+                verify_result: true,
+            }],
+            da_submissions: vec![DaSubmissionRecord {
+                proof_id: "proof".to_string(),
+                height,
+                tx_hash: "tx".to_string(),
+                submitter: "submitter".to_string(),
+                namespace: "test-namespace".to_string(),
+                data_location: "NUBITDA".to_string(),
+                da_block_hash: "da-block".to_string(),
+                da_block_height: 9,
+                da_tx_hash: "da-tx".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn write_and_read_back() {
+        let path = std::env::temp_dir().join(format!("fiamma-index-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut store = SqliteStore::open(&path).unwrap();
+        assert_eq!(store.checkpoint().unwrap(), None);
+        let block = block(12);
+        store.write_block(&block).unwrap();
+
+        // Reopening resumes from the persisted checkpoint.
+        let store = SqliteStore::open(&path).unwrap();
+        assert_eq!(store.checkpoint().unwrap(), Some(12));
+        assert_eq!(store.proof("proof").unwrap(), Some(block.proofs[0].clone()));
+        assert_eq!(
+            store.proofs_by_namespace("test-namespace").unwrap(),
+            block.proofs
+        );
+        assert_eq!(store.verifications("proof").unwrap(), block.verifications);
+        assert_eq!(store.da_submissions("proof").unwrap(), block.da_submissions);
+        assert_eq!(store.proof("missing").unwrap(), None);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    generated::fiamma::bitvmstaker::StakerInfo,
+    types::{MsgCreateStaker, MsgRemoveStaker},
+    validation::validate_staker_address,
+};
+use cosmrs::{tx::Msg, AccountId, Any, ErrorReport, Result};
+use std::{collections::BTreeSet, fmt, fs, path::Path};
+
+/// A set of `fiammavaloper` staker addresses.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StakerSet {
+    addresses: BTreeSet<String>,
+}
+
+impl StakerSet {
+    /// Builds a set from `addresses`, rejecting any that is not a `fiammavaloper` address.
+    pub fn new<I, S>(addresses: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let addresses = addresses
+            .into_iter()
+            .map(|address| {
+                let address = address.into();
+                validate_staker_address(&address)?;
+                Ok(address)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { addresses })
+    }
+
+    /// Reads a desired set from a file with one address per line. Blank lines and lines
+    /// starting with `#` are ignored.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| {
+            ErrorReport::msg(format!(
+                "Failed to read staker set {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::new(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        )
+    }
+
+    /// The set as stored on chain. Addresses are taken as is.
+    pub(crate) fn from_staker_info(staker_info: &[StakerInfo]) -> Self {
+        Self {
+            addresses: staker_info
+                .iter()
+                .map(|info| info.staker_address.clone())
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, address: &str) -> bool {
+        self.addresses.contains(address)
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.addresses.iter().map(String::as_str)
+    }
+
+    /// The changes that turn this set into `desired`.
+    pub fn diff(&self, desired: &StakerSet) -> StakerSetDiff {
+        StakerSetDiff {
+            to_add: desired
+                .addresses
+                .difference(&self.addresses)
+                .cloned()
+                .collect(),
+            to_remove: self
+                .addresses
+                .difference(&desired.addresses)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Stakers to create and remove, each sorted by address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakerSetDiff {
+    pub to_add: Vec<String>,
+    pub to_remove: Vec<String>,
+}
+
+impl StakerSetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.to_add.is_empty() && self.to_remove.is_empty()
+    }
+
+    /// One `MsgRemoveStaker` per removed staker followed by one `MsgCreateStaker` per added
+    /// staker, signed by `creator` (the committee address).
+    pub fn msgs(&self, creator: &AccountId) -> Result<Vec<Any>> {
+        let removes = self.to_remove.iter().map(|staker_address| {
+            MsgRemoveStaker {
+                creator: creator.clone(),
+                staker_address: staker_address.clone(),
+            }
+            .to_any()
+        });
+        let creates = self.to_add.iter().map(|staker_address| {
+            MsgCreateStaker {
+                creator: creator.clone(),
+                staker_address: staker_address.clone(),
+            }
+            .to_any()
+        });
+        removes.chain(creates).collect()
+    }
+}
+
+/// One line per change, `- <address>` for removals and `+ <address>` for additions.
+impl fmt::Display for StakerSetDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no staker changes");
+        }
+        let removes = self.to_remove.iter().map(|address| ('-', address));
+        let adds = self.to_add.iter().map(|address| ('+', address));
+        for (i, (sign, address)) in removes.chain(adds).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{} {}", sign, address)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StakerSet;
+    use crate::{
+        generated::fiamma::bitvmstaker::StakerInfo,
+        types::{MsgCreateStaker, MsgRemoveStaker},
+        validation::ValidationError,
+        wallet::Wallet,
+    };
+    use cosmrs::tx::Msg;
+
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const STAKER_A: &str = "fiammavaloper124lzt3g4axrqf8p58f6hs9uzdk056y3cey2m5r";
+
+    fn staker(account: &str) -> String {
+        let account_id: cosmrs::AccountId = account.parse().unwrap();
+        cosmrs::AccountId::new("fiammavaloper", &account_id.to_bytes())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn diff_and_msgs() {
+        let staker_b = staker(Wallet::new(PRIVATE_KEY).account_id.as_ref());
+        let current = StakerSet::from_staker_info(&[
+            StakerInfo {
+                staker_index: 0,
+                staker_address: STAKER_A.to_string(),
+            },
+            StakerInfo {
+                staker_index: 1,
+                staker_address: "fiammavaloper1stale".to_string(),
+            },
+        ]);
+        let desired = StakerSet::new([STAKER_A, &staker_b]).unwrap();
+
+        let diff = current.diff(&desired);
+        assert_eq!(diff.to_add, vec![staker_b.clone()]);
+        assert_eq!(diff.to_remove, vec!["fiammavaloper1stale".to_string()]);
+        assert_eq!(
+            diff.to_string(),
+            format!("- fiammavaloper1stale\n+ {}", staker_b)
+        );
+        assert!(desired.diff(&desired).is_empty());
+
+        let creator = Wallet::new(PRIVATE_KEY).account_id;
+        let msgs = diff.msgs(&creator).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                MsgRemoveStaker {
+                    creator: creator.clone(),
+                    staker_address: "fiammavaloper1stale".to_string(),
+                }
+                .to_any()
+                .unwrap(),
+                MsgCreateStaker {
+                    creator,
+                    staker_address: staker_b,
+                }
+                .to_any()
+                .unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_non_valoper_addresses() {
+        let account = Wallet::new(PRIVATE_KEY).account_id.to_string();
+        assert_eq!(
+            StakerSet::new([account.clone()]).unwrap_err().to_string(),
+            ValidationError::InvalidStakerAddress(account).to_string()
+        );
+        assert!(StakerSet::new(["fiammavaloper1invalid"]).is_err());
+    }
+
+    #[test]
+    fn read_from_file() {
+        let path = std::env::temp_dir().join(format!("fiamma-stakers-{}.txt", std::process::id()));
+        std::fs::write(&path, format!("# committee stakers\n\n  {}\n", STAKER_A)).unwrap();
+        let set = StakerSet::from_file(&path).unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![STAKER_A]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,12 +1,24 @@
 #![allow(dead_code)]
 
 use crate::{
+    amino::{self, StdSignDoc},
+    authz::{MsgExec, MsgGrant, MsgRevoke},
+    bank_query::QueryClient as BankQueryClient,
+    bitvmstaker_query::QueryClient as BitvmstakerQueryClient,
     chain::*,
+    decoder::DecodedTx,
+    error_hook::ErrorHook,
+    fee_payer::{FeePayerTx, TxSigner},
+    multisig::{MultisigAccount, MultisigTx, PartialSignature},
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
+    staker_set::StakerSetDiff,
     types::{
         MsgCreateStaker, MsgRegisterVK, MsgRemoveStaker, MsgRemoveVK,
-        MsgSubmitCommunityVerification, MsgSubmitProof,
+        MsgSubmitCommunityVerification, MsgSubmitProof, MsgUpdateDASubmissionResults,
     },
-    wallet::Wallet,
+    vk_registry::{VkRegistry, VkSyncPlan},
+    wallet::{self, Wallet},
 };
 use cosmos_sdk_proto::cosmos::{
     base::abci::v1beta1::TxResponse,
@@ -16,38 +28,208 @@ use cosmos_sdk_proto::cosmos::{
     },
 };
 use cosmrs::{
-    tx::{BodyBuilder, Fee, Msg, Raw, SignDoc, SignerInfo},
+    bank::MsgSend,
+    crypto::PublicKey,
+    feegrant::{MsgGrantAllowance, MsgRevokeAllowance},
+    tx::{
+        AccountNumber, BodyBuilder, Fee, ModeInfo, Msg, Raw, SequenceNumber, SignDoc, SignMode,
+        SignerInfo,
+    },
     AccountId, Any, Coin, Denom, ErrorReport, Result,
 };
-use std::str::FromStr;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tonic::{transport::Channel, Code, Status};
+
+/// How long broadcasts stick to one node after the last broadcast.
+pub const DEFAULT_PIN_WINDOW: Duration = Duration::from_secs(60);
+/// How often [`TxClient::wait_for_tx`] asks the node for the transaction.
+pub const INCLUSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// `ErrWrongSequence` in the `sdk` codespace.
+const WRONG_SEQUENCE_CODE: u32 = 32;
+
+#[cfg(feature = "local-verifier")]
+use crate::{local_verifier::LocalVerification, types::ProofSystem};
 
 #[derive(Debug, Clone)]
 pub struct TxClient {
     pub wallet: Wallet,
+    /// The first endpoint of [`TxClient::nodes`]. Requests are routed through `nodes`, so
+    /// changing this field has no effect.
+    #[deprecated(note = "requests go through `nodes`; read the endpoint with `TxClient::rpc()`")]
     pub rpc: String,
+    pub nodes: NodePool,
     pub fee: u128,
     pub gas_limit: u64,
+    pub sign_mode: SignMode,
+    pub fee_granter: Option<AccountId>,
+    pub retry_policy: RetryPolicy,
+    pub pin_window: Duration,
+    pub check_funds: bool,
+    pub validate_proofs: bool,
+    pub error_hook: ErrorHook,
+    #[cfg(feature = "local-verifier")]
+    pub local_verification: LocalVerification,
+    pinned: Arc<Mutex<Option<(usize, Instant)>>>,
+    next_sequence: Arc<tokio::sync::Mutex<Option<SequenceNumber>>>,
 }
 
 impl TxClient {
     pub fn new(private_key: &str, rpc: &str, fee: u128, gas_limit: u64) -> Self {
+        Self::from_nodes(private_key, NodePool::new(&[rpc]), fee, gas_limit)
+    }
+
+    /// A client that sends requests to the healthiest of `nodes`.
+    #[allow(deprecated)]
+    pub fn from_nodes(private_key: &str, nodes: NodePool, fee: u128, gas_limit: u64) -> Self {
         let wallet = Wallet::new(private_key);
         Self {
             wallet,
-            rpc: rpc.to_string(),
+            rpc: nodes.primary().to_string(),
+            nodes,
             fee,
             gas_limit,
+            sign_mode: SignMode::Direct,
+            fee_granter: None,
+            retry_policy: RetryPolicy::default(),
+            pin_window: DEFAULT_PIN_WINDOW,
+            check_funds: false,
+            validate_proofs: false,
+            error_hook: ErrorHook::default(),
+            #[cfg(feature = "local-verifier")]
+            local_verification: LocalVerification::Off,
+            pinned: Arc::new(Mutex::new(None)),
+            next_sequence: Arc::new(tokio::sync::Mutex::new(None)),
         }
     }
 
+    pub fn with_sign_mode(mut self, sign_mode: SignMode) -> Self {
+        self.sign_mode = sign_mode;
+        self
+    }
+
+    /// Deduct fees from `granter`'s `x/feegrant` allowance to this client's account.
+    pub fn with_fee_granter(mut self, granter: AccountId) -> Self {
+        self.fee_granter = Some(granter);
+        self
+    }
+
+    /// Verify Groth16 proofs locally in [`TxClient::submit_proof`] before broadcasting. With
+    /// [`LocalVerification::Warn`] failures go to the client's [`ErrorHook`]. Proof systems the
+    /// local verifier cannot check, such as FFLONK, are broadcast unchecked.
+    #[cfg(feature = "local-verifier")]
+    pub fn with_local_verification(mut self, local_verification: LocalVerification) -> Self {
+        self.local_verification = local_verification;
+        self
+    }
+
+    /// Retry policy for every gRPC call the client makes. Broadcasts are never resent once
+    /// they may have reached the node.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Send requests to the healthiest of `nodes` instead of the single endpoint given to
+    /// `new`.
+    #[allow(deprecated)]
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.rpc = nodes.primary().to_string();
+        self.nodes = nodes;
+        self.pinned = Arc::new(Mutex::new(None));
+        self
+    }
+
+    /// Account queries and broadcasts stay on one node until `pin_window` passes without a
+    /// broadcast or the node turns unhealthy, so consecutive sequence numbers reach the same
+    /// mempool.
+    pub fn with_pin_window(mut self, pin_window: Duration) -> Self {
+        self.pin_window = pin_window;
+        self
+    }
+
+    /// Check the fee payer can afford the fee before each broadcast, failing early instead of
+    /// having the transaction rejected by the node.
+    pub fn with_funds_check(mut self, check_funds: bool) -> Self {
+        self.check_funds = check_funds;
+        self
+    }
+
+    /// Run [`MsgSubmitProof::validate`] in [`TxClient::submit_proof`], refusing malformed
+    /// proofs before broadcasting. Off by default, as the namespace convention it applies is
+    /// stricter than the chain.
+    pub fn with_proof_validation(mut self, validate_proofs: bool) -> Self {
+        self.validate_proofs = validate_proofs;
+        self
+    }
+
+    /// Receives warnings the client recovers from, such as local verification failures in
+    /// [`LocalVerification::Warn`] mode.
+    pub fn with_error_hook(mut self, error_hook: ErrorHook) -> Self {
+        self.error_hook = error_hook;
+        self
+    }
+
+    /// The first endpoint of [`TxClient::nodes`], replacing the deprecated `rpc` field.
+    pub fn rpc(&self) -> &str {
+        self.nodes.primary()
+    }
+
     pub fn account_id(&self) -> AccountId {
         self.wallet.account_id.clone()
     }
 
     pub async fn submit_proof(&self, msg: MsgSubmitProof) -> Result<BroadcastTxResponse> {
+        if self.validate_proofs {
+            msg.validate()?;
+        }
+        #[cfg(feature = "local-verifier")]
+        self.verify_locally(&msg)?;
         self.construct_broadcast_tx(msg.to_any()?).await
     }
 
+    #[cfg(feature = "local-verifier")]
+    fn verify_locally(&self, msg: &MsgSubmitProof) -> Result<()> {
+        let supported =
+            ProofSystem::from_str_name(&msg.proof_system) == Some(ProofSystem::Groth16Bn254Bitvm);
+        if self.local_verification == LocalVerification::Off || !supported {
+            return Ok(());
+        }
+        let reason = match msg.verify_locally() {
+            Ok(true) => return Ok(()),
+            Ok(false) => "proof does not verify against the vk".to_string(),
+            Err(e) => e.to_string(),
+        };
+        let error = ErrorReport::msg(format!("Local verification failed: {}", reason));
+        match self.local_verification {
+            LocalVerification::Refuse => Err(error),
+            _ => {
+                self.error_hook.report(&error);
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs [`MsgSubmitProof::validate`] and checks the VK against the on-chain registered
+    /// VK list, without broadcasting. This is the way to check registration before
+    /// [`TxClient::submit_proof`], which never queries the registry itself.
+    pub async fn validate_proof(&self, msg: &MsgSubmitProof) -> Result<()> {
+        msg.validate()?;
+        self.vk_registry().await?.check_registered(&msg.vk)?;
+        Ok(())
+    }
+
+    /// The VKs currently registered on chain.
+    pub async fn vk_registry(&self) -> Result<VkRegistry> {
+        BitvmstakerQueryClient::from_nodes(self.nodes.clone())
+            .with_retry_policy(self.retry_policy.clone())
+            .get_vk_registry()
+            .await
+    }
+
     pub async fn submit_community_verification(
         &self,
         msg: MsgSubmitCommunityVerification,
@@ -55,6 +237,22 @@ impl TxClient {
         self.construct_broadcast_tx(msg.to_any()?).await
     }
 
+    /// Submits several community verification votes in a single transaction.
+    pub async fn submit_community_verifications(
+        &self,
+        msgs: Vec<MsgSubmitCommunityVerification>,
+    ) -> Result<BroadcastTxResponse> {
+        let msgs = msgs.iter().map(Msg::to_any).collect::<Result<Vec<_>>>()?;
+        self.construct_broadcast_txs(msgs).await
+    }
+
+    pub async fn update_da_submission_results(
+        &self,
+        msg: MsgUpdateDASubmissionResults,
+    ) -> Result<BroadcastTxResponse> {
+        self.construct_broadcast_tx(msg.to_any()?).await
+    }
+
     pub async fn create_staker(&self, msg: MsgCreateStaker) -> Result<BroadcastTxResponse> {
         self.construct_broadcast_tx(msg.to_any()?).await
     }
@@ -63,6 +261,29 @@ impl TxClient {
         self.construct_broadcast_tx(msg.to_any()?).await
     }
 
+    /// Broadcasts the create and remove messages of `diff` in a single transaction, or does
+    /// nothing if the diff is empty.
+    pub async fn apply_staker_diff(
+        &self,
+        diff: &StakerSetDiff,
+    ) -> Result<Option<BroadcastTxResponse>> {
+        if diff.is_empty() {
+            return Ok(None);
+        }
+        let msgs = diff.msgs(&self.account_id())?;
+        self.construct_broadcast_txs(msgs).await.map(Some)
+    }
+
+    /// Broadcasts the register and remove messages of `plan` in a single transaction, or does
+    /// nothing if the plan is empty.
+    pub async fn apply_vk_sync(&self, plan: &VkSyncPlan) -> Result<Option<BroadcastTxResponse>> {
+        if plan.is_empty() {
+            return Ok(None);
+        }
+        let msgs = plan.msgs(&self.account_id())?;
+        self.construct_broadcast_txs(msgs).await.map(Some)
+    }
+
     pub async fn register_vk(&self, msg: MsgRegisterVK) -> Result<BroadcastTxResponse> {
         self.construct_broadcast_tx(msg.to_any()?).await
     }
@@ -71,9 +292,230 @@ impl TxClient {
         self.construct_broadcast_tx(msg.to_any()?).await
     }
 
+    pub async fn grant_allowance(&self, msg: MsgGrantAllowance) -> Result<BroadcastTxResponse> {
+        self.construct_broadcast_tx(msg.to_any()?).await
+    }
+
+    pub async fn revoke_allowance(&self, msg: MsgRevokeAllowance) -> Result<BroadcastTxResponse> {
+        self.construct_broadcast_tx(msg.to_any()?).await
+    }
+
+    pub async fn grant(&self, msg: MsgGrant) -> Result<BroadcastTxResponse> {
+        self.construct_broadcast_tx(msg.to_any()?).await
+    }
+
+    pub async fn revoke(&self, msg: MsgRevoke) -> Result<BroadcastTxResponse> {
+        self.construct_broadcast_tx(msg.to_any()?).await
+    }
+
+    /// Transfers `amount` ufia from this client's account to `to`.
+    pub async fn send(&self, to: AccountId, amount: u128) -> Result<BroadcastTxResponse> {
+        let msg = MsgSend {
+            from_address: self.account_id(),
+            to_address: to,
+            amount: vec![Coin {
+                amount,
+                denom: Denom::from_str(DENOM)?,
+            }],
+        };
+        self.broadcast_spending(vec![msg.to_any()?], amount).await
+    }
+
+    /// Sends `address` enough ufia to reach `target_balance` if its balance is below
+    /// `min_balance`. Returns `None` when no transfer was needed.
+    pub async fn top_up(
+        &self,
+        address: AccountId,
+        min_balance: u128,
+        target_balance: u128,
+    ) -> Result<Option<BroadcastTxResponse>> {
+        let balance = self.bank_query_client().get_balance(&address).await?;
+        if balance >= min_balance {
+            return Ok(None);
+        }
+        let amount = target_balance.saturating_sub(balance);
+        if amount == 0 {
+            return Ok(None);
+        }
+        self.send(address, amount).await.map(Some)
+    }
+
+    /// The ufia balance of this client's account.
+    pub async fn balance(&self) -> Result<u128> {
+        self.bank_query_client()
+            .get_balance(&self.account_id())
+            .await
+    }
+
+    /// Fails if this client's account cannot afford the fee plus `spend` ufia. Fees drawn
+    /// from a fee granter's allowance are not checked.
+    pub async fn check_sufficient_funds(&self, spend: u128) -> Result<()> {
+        self.check_funds_with_payer(None, spend).await
+    }
+
+    /// Like [`TxClient::check_sufficient_funds`], with the fee charged to `payer` if given.
+    async fn check_funds_with_payer(&self, payer: Option<&AccountId>, spend: u128) -> Result<()> {
+        let account_id = self.account_id();
+        let fee = if self.fee_granter.is_some() {
+            0
+        } else {
+            self.fee
+        };
+        let mut required = vec![(account_id, spend)];
+        match payer {
+            Some(payer) => required.push((payer.clone(), fee)),
+            None => required[0].1 += fee,
+        }
+
+        let query_client = self.bank_query_client();
+        for (address, required) in required {
+            if required == 0 {
+                continue;
+            }
+            let balance = query_client.get_spendable_balance(&address).await?;
+            if balance < required {
+                return Err(ErrorReport::msg(format!(
+                    "Insufficient funds: {} has {}{}, needs {}{}",
+                    address, balance, DENOM, required, DENOM
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn bank_query_client(&self) -> BankQueryClient {
+        BankQueryClient::from_nodes(self.nodes.clone()).with_retry_policy(self.retry_policy.clone())
+    }
+
+    /// Wraps `msgs` in a `MsgExec` signed by this client's account as grantee.
+    pub async fn exec(&self, msgs: Vec<Any>) -> Result<BroadcastTxResponse> {
+        let msg = MsgExec {
+            grantee: self.account_id(),
+            msgs,
+        };
+        self.construct_broadcast_tx(msg.to_any()?).await
+    }
+
+    pub async fn construct_multisig_tx(
+        &self,
+        multisig: &MultisigAccount,
+        signers: &[PublicKey],
+        msgs: Vec<Any>,
+    ) -> Result<MultisigTx> {
+        if self.sign_mode == SignMode::LegacyAminoJson {
+            amino::check_amino_json(&msgs)?;
+        }
+        let account = wallet::account_info(self.pinned_channel()?, &multisig.account_id).await?;
+        let (account_number, sequence) =
+            account.map_or((0, 0), |acc| (acc.account_number, acc.sequence));
+
+        let chain_id = CHAIN_ID.parse()?;
+        let tx_body = BodyBuilder::new().msgs(msgs).finish();
+        MultisigTx::new(
+            multisig,
+            signers,
+            &tx_body,
+            self.fee()?,
+            &chain_id,
+            account_number,
+            sequence,
+            self.sign_mode,
+        )
+    }
+
+    /// Builds and signs a transaction whose fee is paid by `payer`. The payer must add its own
+    /// signature with [`FeePayerTx::sign`] before [`TxClient::broadcast_fee_payer_tx`].
+    pub async fn construct_fee_payer_tx(
+        &self,
+        payer: &PublicKey,
+        msgs: Vec<Any>,
+    ) -> Result<(FeePayerTx, PartialSignature)> {
+        if self.sign_mode != SignMode::Direct {
+            return Err(ErrorReport::msg(
+                "Fee payer transactions are only supported with SIGN_MODE_DIRECT",
+            ));
+        }
+        let payer_id = payer.account_id(ACCOUNT_PREFIX)?;
+        let account = wallet::account_info(self.pinned_channel()?, &self.wallet.account_id).await?;
+        let payer_account = wallet::account_info(self.pinned_channel()?, &payer_id)
+            .await?
+            .ok_or(ErrorReport::msg(format!(
+                "Fee payer account {} does not exist",
+                payer_id
+            )))?;
+        let (account_number, sequence) =
+            account.map_or((0, 0), |acc| (acc.account_number, acc.sequence));
+
+        let tx_body = BodyBuilder::new().msgs(msgs).finish();
+        let tx = FeePayerTx::new(
+            &tx_body,
+            self.fee()?,
+            &CHAIN_ID.parse()?,
+            TxSigner {
+                public_key: self.wallet.public_key,
+                account_number,
+                sequence,
+            },
+            TxSigner {
+                public_key: *payer,
+                account_number: payer_account.account_number,
+                sequence: payer_account.sequence,
+            },
+        )?;
+        let signature = tx.sign(&self.wallet)?;
+        Ok((tx, signature))
+    }
+
+    /// Broadcasts `tx` once both the submitter and the payer have signed it.
+    pub async fn broadcast_fee_payer_tx(
+        &self,
+        tx: FeePayerTx,
+        signatures: Vec<PartialSignature>,
+    ) -> Result<BroadcastTxResponse> {
+        if self.check_funds {
+            self.check_funds_with_payer(Some(&tx.payer()?), 0).await?;
+        }
+        let raw_tx = tx.aggregate(signatures)?;
+        self.broadcast_tx(raw_tx).await
+    }
+
+    pub async fn broadcast_multisig_tx(
+        &self,
+        tx: MultisigTx,
+        signatures: Vec<PartialSignature>,
+    ) -> Result<BroadcastTxResponse> {
+        let raw_tx = tx.aggregate(signatures)?;
+        self.broadcast_tx(raw_tx).await
+    }
+
     async fn construct_broadcast_tx(&self, msg: impl Into<Any>) -> Result<BroadcastTxResponse> {
-        let raw_tx = self.construct_tx(msg).await?;
-        let mut client = ServiceClient::connect(self.rpc.clone()).await?;
+        self.construct_broadcast_txs(vec![msg.into()]).await
+    }
+
+    async fn construct_broadcast_txs(&self, msgs: Vec<Any>) -> Result<BroadcastTxResponse> {
+        self.broadcast_spending(msgs, 0).await
+    }
+
+    /// Broadcasts `msgs`, which move `spend` ufia out of this client's account.
+    ///
+    /// Transactions from one client (and its clones) are signed and broadcast one at a time,
+    /// and each is signed with the sequence after the last one the node accepted, so several
+    /// can be broadcast before the first is committed.
+    async fn broadcast_spending(&self, msgs: Vec<Any>, spend: u128) -> Result<BroadcastTxResponse> {
+        if self.check_funds {
+            self.check_sufficient_funds(spend).await?;
+        }
+        let mut next_sequence = self.next_sequence.lock().await;
+        let (raw_tx, sequence) = self.construct_tx(msgs, *next_sequence).await?;
+        let resp = self.broadcast_tx(raw_tx).await?;
+        *next_sequence =
+            sequence_after_broadcast(*next_sequence, sequence, resp.tx_response.as_ref());
+        Ok(resp)
+    }
+
+    async fn broadcast_tx(&self, raw_tx: Raw) -> Result<BroadcastTxResponse> {
+        let (index, channel) = self.pinned_node()?;
+        let mut client = ServiceClient::new(channel);
         let tx_commit_response = client
             .broadcast_tx(BroadcastTxRequest {
                 tx_bytes: raw_tx.to_bytes()?,
@@ -81,32 +523,166 @@ impl TxClient {
             })
             .await;
 
-        tx_commit_response
-            .map(|resp| resp.get_ref().clone())
-            .map_err(ErrorReport::from)
+        match tx_commit_response {
+            Ok(resp) => {
+                if let Some((_, until)) = self.pinned.lock().unwrap().as_mut() {
+                    *until = Instant::now() + self.pin_window;
+                }
+                Ok(resp.into_inner())
+            }
+            Err(status) => {
+                if status.code() == Code::Unavailable {
+                    self.unpin_failed(index);
+                }
+                Err(status.into())
+            }
+        }
     }
 
-    async fn construct_tx(&self, msg: impl Into<Any>) -> Result<Raw> {
-        let account = self.wallet.get_account_info(self.rpc.clone()).await;
-        let (account_number, sequence) =
+    /// Signs `msgs` with the committed account sequence, or `next_sequence` if that is ahead
+    /// of it, returning the transaction and the sequence used.
+    pub(crate) async fn construct_tx(
+        &self,
+        msgs: Vec<Any>,
+        next_sequence: Option<SequenceNumber>,
+    ) -> Result<(Raw, SequenceNumber)> {
+        if self.sign_mode == SignMode::LegacyAminoJson {
+            amino::check_amino_json(&msgs)?;
+        }
+        let (index, channel) = self.pinned_node()?;
+        let account = match wallet::account_info(channel, &self.wallet.account_id).await {
+            Ok(account) => account,
+            Err(e) => {
+                if e.downcast_ref::<Status>()
+                    .is_some_and(|status| status.code() == Code::Unavailable)
+                {
+                    self.unpin_failed(index);
+                }
+                return Err(e);
+            }
+        };
+        let (account_number, committed) =
             account.map_or((0, 0), |acc| (acc.account_number, acc.sequence));
+        let sequence = next_sequence.map_or(committed, |next| next.max(committed));
+        Ok((self.sign_tx(msgs, account_number, sequence)?, sequence))
+    }
 
+    /// Signs `msgs` as this client's account with the given account number and sequence,
+    /// without querying the node. Messages are assumed to have passed the amino JSON check
+    /// in [`TxClient::construct_tx`].
+    pub(crate) fn sign_tx(
+        &self,
+        msgs: Vec<Any>,
+        account_number: AccountNumber,
+        sequence: SequenceNumber,
+    ) -> Result<Raw> {
         let chain_id = CHAIN_ID.parse()?;
+        let tx_body = BodyBuilder::new().msgs(msgs).finish();
+        match self.sign_mode {
+            SignMode::Direct => {
+                let auth_info = SignerInfo::single_direct(Some(self.wallet.public_key), sequence)
+                    .auth_info(self.fee()?);
+                let sign_doc = SignDoc::new(&tx_body, &auth_info, &chain_id, account_number)?;
+                self.wallet.sign(sign_doc)
+            }
+            SignMode::LegacyAminoJson => {
+                let fee = self.fee()?;
+                let sign_doc = StdSignDoc::new(&tx_body, &fee, CHAIN_ID, account_number, sequence)?;
+                let auth_info = SignerInfo {
+                    public_key: Some(self.wallet.public_key.into()),
+                    mode_info: ModeInfo::single(SignMode::LegacyAminoJson),
+                    sequence,
+                }
+                .auth_info(fee);
+                self.wallet.sign_amino(tx_body, auth_info, sign_doc)
+            }
+            mode => Err(ErrorReport::msg(format!(
+                "Unsupported sign mode {}",
+                mode.as_str_name()
+            ))),
+        }
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.nodes.channel(&self.retry_policy)
+    }
+
+    /// A channel to the node transactions are currently pinned to.
+    fn pinned_channel(&self) -> Result<RetryService<Channel>> {
+        Ok(self.pinned_node()?.1)
+    }
+
+    /// The index of the node transactions are currently pinned to, and a channel to it.
+    fn pinned_node(&self) -> Result<(usize, RetryService<Channel>)> {
+        let mut pinned = self.pinned.lock().unwrap();
+        let index = match *pinned {
+            Some((index, until)) if Instant::now() < until && self.nodes.is_healthy(index) => index,
+            _ => {
+                let index = self.nodes.best().unwrap_or(0);
+                *pinned = Some((index, Instant::now() + self.pin_window));
+                index
+            }
+        };
+        Ok((index, self.nodes.pinned_channel(index, &self.retry_policy)?))
+    }
+
+    /// Marks node `index` failed and drops the pin to it, so the next transaction goes to
+    /// another node instead of waiting for the pin window to pass.
+    fn unpin_failed(&self, index: usize) {
+        self.nodes.mark_failed(index);
+        let mut pinned = self.pinned.lock().unwrap();
+        if matches!(*pinned, Some((pinned_index, _)) if pinned_index == index) {
+            *pinned = None;
+        }
+    }
+
+    fn fee(&self) -> Result<Fee> {
         let fee = Coin {
             amount: self.fee,
             denom: Denom::from_str(DENOM)?,
         };
-        let fee = Fee::from_amount_and_gas(fee, self.gas_limit);
-        let tx_body = BodyBuilder::new().msg(msg).finish();
-        let auth_info =
-            SignerInfo::single_direct(Some(self.wallet.public_key), sequence).auth_info(fee);
-        let sign_doc = SignDoc::new(&tx_body, &auth_info, &chain_id, account_number)?;
-        self.wallet.sign(sign_doc)
+        let mut fee = Fee::from_amount_and_gas(fee, self.gas_limit);
+        fee.granter = self.fee_granter.clone();
+        Ok(fee)
+    }
+
+    pub async fn get_decoded_tx(&self, tx_id: &str) -> Result<DecodedTx> {
+        DecodedTx::from_tx_response(&self.get_tx(tx_id).await?)
+    }
+
+    /// Polls `GetTx` until `tx_hash` is in a block, failing once `timeout` passes. A
+    /// broadcast response with code 0 only means the transaction passed `CheckTx`; the
+    /// returned response carries the code it was executed with.
+    pub async fn wait_for_tx(&self, tx_hash: &str, timeout: Duration) -> Result<TxResponse> {
+        let deadline = Instant::now() + timeout;
+        let mut client = ServiceClient::new(self.channel());
+        loop {
+            let request = GetTxRequest {
+                hash: tx_hash.to_string(),
+            };
+            match client.get_tx(request).await {
+                Ok(resp) => {
+                    return resp.into_inner().tx_response.ok_or(ErrorReport::msg(
+                        "Failed to parse TxResponse while waiting for tx",
+                    ))
+                }
+                Err(status) if status.code() == Code::NotFound && Instant::now() < deadline => {
+                    tokio::time::sleep(INCLUSION_POLL_INTERVAL).await
+                }
+                Err(status) if status.code() == Code::NotFound => {
+                    return Err(ErrorReport::msg(format!(
+                        "Tx {} was not included within {:?}",
+                        tx_hash, timeout
+                    )))
+                }
+                Err(status) => return Err(status.into()),
+            }
+        }
     }
 
     // TODO: This use ServiceClient, but do not need private_key, fee, gas_limit, refactor it!
     pub async fn get_tx(&self, tx_id: &str) -> Result<TxResponse> {
-        let mut client = ServiceClient::connect(self.rpc.clone()).await?;
+        let mut client = ServiceClient::new(self.channel());
         let resp = client
             .get_tx(GetTxRequest {
                 hash: tx_id.to_string(),
@@ -121,20 +697,71 @@ impl TxClient {
     }
 }
 
+/// The sequence to sign the next transaction with after broadcasting one at `sequence`: the
+/// one after it if the node accepted it, the sequence the node expects on a mismatch, and
+/// otherwise unchanged.
+fn sequence_after_broadcast(
+    next_sequence: Option<SequenceNumber>,
+    sequence: SequenceNumber,
+    response: Option<&TxResponse>,
+) -> Option<SequenceNumber> {
+    match response {
+        Some(resp) if resp.code == 0 => Some(sequence + 1),
+        Some(resp) if resp.codespace == "sdk" && resp.code == WRONG_SEQUENCE_CODE => {
+            expected_sequence(&resp.raw_log)
+        }
+        _ => next_sequence,
+    }
+}
+
+/// Parses the SDK's "account sequence mismatch, expected 5, got 4" log.
+fn expected_sequence(raw_log: &str) -> Option<SequenceNumber> {
+    raw_log
+        .split("expected ")
+        .nth(1)?
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{MsgCreateStaker, MsgRemoveStaker, MsgSubmitCommunityVerification, MsgSubmitProof};
-    use crate::{tx::TxClient, wallet::Wallet};
-    use cosmrs::AccountId;
+    use super::{
+        sequence_after_broadcast, MsgCreateStaker, MsgRemoveStaker, MsgSubmitCommunityVerification,
+        MsgSubmitProof,
+    };
+    use crate::{
+        authz::{type_url, MsgGrant},
+        chain::DENOM,
+        multisig::MultisigAccount,
+        node_pool::NodePool,
+        proof_artifacts::ProofArtifacts,
+        retry::RetryPolicy,
+        tx::TxClient,
+        types::ProofSystem,
+        wallet::Wallet,
+    };
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+    use cosmrs::{
+        bank::MsgSend,
+        feegrant::{BasicAllowance, MsgGrantAllowance},
+        tx::{Msg, Tx},
+        AccountId, Coin,
+    };
     use sha2::{Digest, Sha256};
 
     const BITVM_PROOF_SYSTEM: &str = "GROTH16_BN254_BITVM";
     const NAMESPACE: &str = "test-namespace";
     const TEST_DATA: &str = "test-data";
+    const FFLONK_TEST_DATA: &str = "test-data/fflonk";
     const DATA_LOCATION: &str = "FIAMMA";
     const SENDER_PRIVATE_KEY: &str =
         "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+    const GRANTEE_PRIVATE_KEY: &str =
+        "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897";
     const NODE: &str = "http://127.0.0.1:9090";
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
     // const NODE: &str = "https://testnet-grpc.fiammachain.io";
     // grpcurl -v -d '{"address":"fiamma19fldhw0awjv2ag7dz0lr3d4qmnfkxz69rzxcdp"}' testnet-grpc.fiammachain.io:443 cosmos.auth.v1beta1.Query/Account
     // fiammad query tx --type=hash 04DD64900B9AB19D2FFB5EE0118BC4C96E3B5F44110E329412BD5EF8B722FADD --node tcp://13.231.104.23:26657 --chain-id fiamma-testnet-1
@@ -188,6 +815,15 @@ mod tests {
         }
     }
 
+    // The FFLONK fixture is hand-made, not snarkjs output or a proof of any circuit, and its
+    // layout is unchecked against the chain, so it is only good for offline tests.
+    fn fflonk_msg_submit_proof(account_id: AccountId) -> MsgSubmitProof {
+        let dir = std::env::current_dir().unwrap().join(FFLONK_TEST_DATA);
+        ProofArtifacts::load_as(dir, ProofSystem::FfplonkBn254Bitvm)
+            .unwrap()
+            .into_msg(account_id, NAMESPACE, DATA_LOCATION)
+    }
+
     #[tokio::test]
     async fn test_submit_proof() {
         let wallet = Wallet::new(SENDER_PRIVATE_KEY);
@@ -213,6 +849,60 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_fflonk_submission_in_tx() {
+        let wallet = Wallet::new(SENDER_PRIVATE_KEY);
+        let tx_client = TxClient::new(SENDER_PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000)
+            .with_retry_policy(RetryPolicy::none())
+            .with_proof_validation(true);
+        let msg = fflonk_msg_submit_proof(wallet.account_id);
+        assert_eq!(msg.proof_system, "FFPLONK_BN254_BITVM");
+        assert_eq!(msg.validate(), Ok(()));
+
+        let raw = tx_client
+            .sign_tx(vec![msg.to_any().unwrap()], 0, 0)
+            .unwrap();
+        let tx = Tx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        assert_eq!(MsgSubmitProof::from_any(&tx.body.messages[0]).unwrap(), msg);
+
+        // FFLONK bytes are left to the chain, so validation does not refuse the submission
+        // and it fails only at the unreachable node.
+        let mut truncated = msg.clone();
+        truncated.proof.truncate(128);
+        let err = tx_client.submit_proof(truncated).await.unwrap_err();
+        assert!(!err.to_string().contains("invalid proof length"));
+    }
+
+    #[cfg(feature = "local-verifier")]
+    #[test]
+    fn local_verification_modes() {
+        use crate::{error_hook::ErrorHook, local_verifier::LocalVerification};
+        use std::sync::{Arc, Mutex};
+
+        let wallet = Wallet::new(SENDER_PRIVATE_KEY);
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        let client = |mode| {
+            TxClient::new(SENDER_PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000)
+                .with_local_verification(mode)
+        };
+        let warn = client(LocalVerification::Warn).with_error_hook(ErrorHook::new(move |e| {
+            sink.lock().unwrap().push(e.to_string())
+        }));
+        let refuse = client(LocalVerification::Refuse);
+
+        let mut wrong_input = msg_submit_proof(wallet.account_id.clone());
+        wrong_input.public_input[0] ^= 1;
+        assert!(refuse.verify_locally(&wrong_input).is_err());
+        assert!(warn.verify_locally(&wrong_input).is_ok());
+        assert_eq!(warnings.lock().unwrap().len(), 1);
+        assert!(warnings.lock().unwrap()[0].starts_with("Local verification failed"));
+
+        // FFLONK proofs can't be checked locally, so they are not refused.
+        let fflonk = fflonk_msg_submit_proof(wallet.account_id);
+        assert!(refuse.verify_locally(&fflonk).is_ok());
+    }
+
     #[tokio::test]
     async fn test_submit_community_verification() {
         let wallet = Wallet::new(SENDER_PRIVATE_KEY);
@@ -260,6 +950,182 @@ mod tests {
         println!("remove_staker resp: {:?}", resp);
     }
 
+    #[tokio::test]
+    async fn test_multisig_create_staker() {
+        let members = [
+            Wallet::new(SENDER_PRIVATE_KEY),
+            Wallet::new("59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897"),
+        ];
+        let public_keys = members.iter().map(|w| w.public_key).collect();
+        let multisig = MultisigAccount::new(2, public_keys).unwrap();
+        let gas_limit = 80_000_000_u64;
+        let fee = 2000_u128;
+        let tx_client = TxClient::new(SENDER_PRIVATE_KEY, NODE, fee, gas_limit);
+        let msg = MsgCreateStaker {
+            creator: multisig.account_id.clone(),
+            staker_address: "fiammavaloper124lzt3g4axrqf8p58f6hs9uzdk056y3cey2m5r".to_string(),
+        };
+        let signers: Vec<_> = members.iter().map(|w| w.public_key).collect();
+        let tx = tx_client
+            .construct_multisig_tx(&multisig, &signers, vec![msg.to_any().unwrap()])
+            .await;
+        let resp = match tx {
+            Ok(tx) => {
+                let signatures = members.iter().map(|w| tx.sign(w).unwrap()).collect();
+                tx_client.broadcast_multisig_tx(tx, signatures).await
+            }
+            Err(e) => Err(e),
+        };
+        println!("multisig create_staker resp: {:?}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_grant_allowance_and_submit_with_fee_granter() {
+        let granter = Wallet::new(SENDER_PRIVATE_KEY);
+        let grantee = Wallet::new(GRANTEE_PRIVATE_KEY);
+        let gas_limit = 80_000_000_u64;
+        let fee = 2000_u128;
+        let granter_client = TxClient::new(SENDER_PRIVATE_KEY, NODE, fee, gas_limit);
+        let allowance = BasicAllowance {
+            spend_limit: vec![Coin {
+                amount: 1_000_000,
+                denom: DENOM.parse().unwrap(),
+            }],
+            expiration: None,
+        };
+        let msg = MsgGrantAllowance {
+            granter: granter.account_id.clone(),
+            grantee: grantee.account_id.clone(),
+            allowance: Some(allowance.to_any().unwrap()),
+        };
+        let resp = granter_client.grant_allowance(msg).await;
+        println!("grant_allowance resp: {:?}", resp);
+
+        let grantee_client = TxClient::new(GRANTEE_PRIVATE_KEY, NODE, fee, gas_limit)
+            .with_fee_granter(granter.account_id.clone());
+        let resp = grantee_client
+            .submit_proof(msg_submit_proof(grantee.account_id.clone()))
+            .await;
+        println!("submit_proof with fee granter resp: {:?}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_broadcasts_pinned_to_one_node() {
+        let nodes = NodePool::new(&[UNREACHABLE_NODE, "http://127.0.0.1:2"]);
+        let tx_client = TxClient::from_nodes(SENDER_PRIVATE_KEY, nodes, 2000, 200_000);
+        let pinned = || tx_client.pinned.lock().unwrap().map(|(index, _)| index);
+
+        tx_client.pinned_channel().unwrap();
+        assert_eq!(pinned(), Some(0));
+        tx_client.pinned_channel().unwrap();
+        assert_eq!(pinned(), Some(0));
+
+        tx_client.nodes.mark_failed(0);
+        tx_client.pinned_channel().unwrap();
+        assert_eq!(pinned(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn failed_broadcast_unpins_node() {
+        let nodes = NodePool::new(&[UNREACHABLE_NODE, "http://127.0.0.1:2"]);
+        let tx_client = TxClient::from_nodes(SENDER_PRIVATE_KEY, nodes, 2000, 200_000)
+            .with_retry_policy(RetryPolicy::none());
+        let msg = MsgSend {
+            from_address: tx_client.account_id(),
+            to_address: tx_client.account_id(),
+            amount: vec![],
+        };
+        let raw_tx = tx_client
+            .sign_tx(vec![msg.to_any().unwrap()], 0, 0)
+            .unwrap();
+
+        assert!(tx_client.broadcast_tx(raw_tx).await.is_err());
+        assert!(tx_client.pinned.lock().unwrap().is_none());
+        assert!(!tx_client.nodes.is_healthy(0));
+        tx_client.pinned_channel().unwrap();
+        assert_eq!(
+            tx_client.pinned.lock().unwrap().map(|(index, _)| index),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fee_granter_in_auth_info() {
+        let granter = Wallet::new(SENDER_PRIVATE_KEY);
+        let grantee = Wallet::new(GRANTEE_PRIVATE_KEY);
+        let tx_client = TxClient::new(GRANTEE_PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000)
+            .with_fee_granter(granter.account_id.clone());
+        let raw = tx_client
+            .sign_tx(
+                vec![msg_submit_proof(grantee.account_id).to_any().unwrap()],
+                0,
+                0,
+            )
+            .unwrap();
+        let tx = Tx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        assert_eq!(tx.auth_info.fee.granter, Some(granter.account_id));
+        assert_eq!(tx.auth_info.fee.payer, None);
+        assert_eq!(tx.auth_info.fee.gas_limit, 200_000);
+    }
+
+    #[tokio::test]
+    async fn test_msg_send_in_tx() {
+        let receiver = Wallet::new(GRANTEE_PRIVATE_KEY);
+        let tx_client = TxClient::new(SENDER_PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000);
+        let msg = MsgSend {
+            from_address: tx_client.account_id(),
+            to_address: receiver.account_id.clone(),
+            amount: vec![Coin {
+                amount: 1_000_000,
+                denom: DENOM.parse().unwrap(),
+            }],
+        };
+        let raw = tx_client
+            .sign_tx(vec![msg.to_any().unwrap()], 0, 0)
+            .unwrap();
+        let tx = Tx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        assert_eq!(MsgSend::from_any(&tx.body.messages[0]).unwrap(), msg);
+    }
+
+    #[tokio::test]
+    async fn test_funds_check() {
+        let granter = Wallet::new(GRANTEE_PRIVATE_KEY);
+        let tx_client = TxClient::new(SENDER_PRIVATE_KEY, UNREACHABLE_NODE, 2000, 200_000)
+            .with_retry_policy(RetryPolicy::none())
+            .with_funds_check(true);
+        // The balance query cannot reach the node, so the check fails before broadcasting.
+        assert!(tx_client.check_sufficient_funds(0).await.is_err());
+        assert!(tx_client.send(granter.account_id.clone(), 1).await.is_err());
+
+        // Granted fees are not drawn from any balance, so there is nothing to query.
+        let tx_client = tx_client.with_fee_granter(granter.account_id);
+        assert!(tx_client.check_sufficient_funds(0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_authz_exec_submit_proof() {
+        let cold = Wallet::new(SENDER_PRIVATE_KEY);
+        let hot = Wallet::new(GRANTEE_PRIVATE_KEY);
+        let gas_limit = 80_000_000_u64;
+        let fee = 2000_u128;
+        let cold_client = TxClient::new(SENDER_PRIVATE_KEY, NODE, fee, gas_limit);
+        let grant = MsgGrant {
+            granter: cold.account_id.clone(),
+            grantee: hot.account_id.clone(),
+            msg_type_url: type_url::<MsgSubmitProof>(),
+            expiration: None,
+        };
+        let resp = cold_client.grant(grant).await;
+        println!("grant resp: {:?}", resp);
+
+        let hot_client = TxClient::new(GRANTEE_PRIVATE_KEY, NODE, fee, gas_limit);
+        let submit_proof_msg = msg_submit_proof(cold.account_id.clone());
+        let resp = hot_client
+            .exec(vec![submit_proof_msg.to_any().unwrap()])
+            .await;
+        println!("exec submit_proof resp: {:?}", resp);
+    }
+
     #[tokio::test]
     async fn test_get_tx() {
         let gas_limit = 80_000_000_u64;
@@ -269,4 +1135,33 @@ mod tests {
         let tx = query_client.get_tx(tx_id).await;
         println!("get_tx tx: {:?}", tx);
     }
+
+    #[test]
+    fn tracks_sequence_across_broadcasts() {
+        let accepted = TxResponse::default();
+        assert_eq!(sequence_after_broadcast(None, 4, Some(&accepted)), Some(5));
+
+        let mismatch = TxResponse {
+            code: 32,
+            codespace: "sdk".to_string(),
+            raw_log: "account sequence mismatch, expected 7, got 5: incorrect account sequence"
+                .to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            sequence_after_broadcast(Some(5), 5, Some(&mismatch)),
+            Some(7)
+        );
+
+        let rejected = TxResponse {
+            code: 5,
+            codespace: "sdk".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            sequence_after_broadcast(Some(5), 5, Some(&rejected)),
+            Some(5)
+        );
+        assert_eq!(sequence_after_broadcast(None, 5, None), None);
+    }
 }
@@ -0,0 +1,317 @@
+#![allow(dead_code)]
+
+use crate::{
+    decoder::DecodedTx,
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
+};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{
+    service_client::ServiceClient, GetTxRequest, GetTxsEventResponse,
+};
+use cosmrs::{
+    proto::traits::{Message, Name},
+    tx::Msg,
+    AccountId, ErrorReport, Result,
+};
+use tonic::{client::Grpc, codec::ProstCodec, codegen::http::uri::PathAndQuery, Request};
+
+pub use cosmos_sdk_proto::cosmos::tx::v1beta1::OrderBy;
+
+/// Event attribute searched for the proof ID of zkpverify messages. The chain emits its
+/// module events from Go code, not from the protos under `src/protos`, and this name has
+/// not been checked against a transaction on chain. If the chain names it differently,
+/// search with [`TxSearch::with_event`] instead.
+pub const PROOF_ID_EVENT: &str = "submit_proof.proof_id";
+/// Event attribute searched for the namespace of zkpverify messages. Unchecked against the
+/// chain, like [`PROOF_ID_EVENT`].
+pub const NAMESPACE_EVENT: &str = "submit_proof.namespace";
+
+const GET_TXS_EVENT_PATH: &str = "/cosmos.tx.v1beta1.Service/GetTxsEvent";
+
+/// `GetTxsEventRequest` including the `query` field added in cosmos-sdk 0.50, which the
+/// cosmos-sdk-proto release in use does not have yet. Nodes before 0.50 read `events`.
+#[derive(Clone, PartialEq, Message)]
+struct GetTxsEventRequest {
+    #[prost(string, repeated, tag = "1")]
+    events: Vec<String>,
+    #[prost(enumeration = "OrderBy", tag = "3")]
+    order_by: i32,
+    #[prost(uint64, tag = "4")]
+    page: u64,
+    #[prost(uint64, tag = "5")]
+    limit: u64,
+    #[prost(string, tag = "6")]
+    query: String,
+}
+
+/// A transaction search over emitted events. All conditions must match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxSearch {
+    conditions: Vec<String>,
+    /// A value given to [`TxSearch::with_event`] that cannot be quoted.
+    invalid_value: Option<String>,
+    order_by: OrderBy,
+    page: u64,
+    limit: u64,
+}
+
+impl Default for TxSearch {
+    fn default() -> Self {
+        Self {
+            conditions: vec![],
+            invalid_value: None,
+            order_by: OrderBy::Asc,
+            page: 1,
+            limit: 100,
+        }
+    }
+}
+
+impl TxSearch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transactions with a message signed by `sender`.
+    pub fn with_sender(self, sender: &AccountId) -> Self {
+        self.with_event("message.sender", sender.as_ref())
+    }
+
+    /// Transactions containing a message of type `M`, e.g. `with_msg_type::<MsgSubmitProof>()`.
+    pub fn with_msg_type<M: Msg>(self) -> Self {
+        self.with_event("message.action", &M::Proto::type_url())
+    }
+
+    pub fn with_proof_id(self, proof_id: &str) -> Self {
+        self.with_event(PROOF_ID_EVENT, proof_id)
+    }
+
+    pub fn with_namespace(self, namespace: &str) -> Self {
+        self.with_event(NAMESPACE_EVENT, namespace)
+    }
+
+    pub fn with_height(mut self, height: u64) -> Self {
+        self.conditions.push(format!("tx.height={}", height));
+        self
+    }
+
+    pub fn with_min_height(mut self, height: u64) -> Self {
+        self.conditions.push(format!("tx.height>={}", height));
+        self
+    }
+
+    pub fn with_max_height(mut self, height: u64) -> Self {
+        self.conditions.push(format!("tx.height<={}", height));
+        self
+    }
+
+    /// Matches `key` (`<event type>.<attribute>`) against `value`. CometBFT queries have no
+    /// escape for `'`, so a value containing one makes the search fail when it is sent.
+    pub fn with_event(mut self, key: &str, value: &str) -> Self {
+        if value.contains('\'') {
+            self.invalid_value.get_or_insert(value.to_string());
+        } else {
+            self.conditions.push(format!("{}='{}'", key, value));
+        }
+        self
+    }
+
+    pub fn with_order_by(mut self, order_by: OrderBy) -> Self {
+        self.order_by = order_by;
+        self
+    }
+
+    /// The 1-based page to fetch.
+    pub fn with_page(mut self, page: u64) -> Self {
+        self.page = page.max(1);
+        self
+    }
+
+    /// Transactions per page. A limit of zero makes the search fail when it is sent.
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// The same search, one page further.
+    pub fn next_page(&self) -> Self {
+        self.clone().with_page(self.page + 1)
+    }
+
+    /// The CometBFT query string sent to the node.
+    pub fn query(&self) -> String {
+        self.conditions.join(" AND ")
+    }
+
+    fn request(&self) -> Result<GetTxsEventRequest> {
+        if let Some(value) = &self.invalid_value {
+            return Err(ErrorReport::msg(format!(
+                "Tx search value {:?} contains a single quote, which CometBFT queries cannot escape",
+                value
+            )));
+        }
+        if self.conditions.is_empty() {
+            return Err(ErrorReport::msg("Tx search needs at least one condition"));
+        }
+        if self.limit == 0 {
+            return Err(ErrorReport::msg("Tx search limit must be at least 1"));
+        }
+        Ok(GetTxsEventRequest {
+            events: self.conditions.clone(),
+            order_by: self.order_by as i32,
+            page: self.page,
+            limit: self.limit,
+            query: self.query(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TxSearchPage {
+    pub txs: Vec<DecodedTx>,
+    /// Number of matching transactions across all pages.
+    pub total: u64,
+    pub page: u64,
+    pub limit: u64,
+}
+
+impl TxSearchPage {
+    pub fn has_next(&self) -> bool {
+        self.page * self.limit < self.total
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryClient {
+    nodes: NodePool,
+    retry_policy: RetryPolicy,
+}
+
+impl QueryClient {
+    pub fn new(rpc: &str) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]))
+    }
+
+    /// A client that routes queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool) -> Self {
+        Self {
+            nodes,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.nodes.channel(&self.retry_policy)
+    }
+
+    pub async fn get_tx(&self, tx_hash: &str) -> Result<DecodedTx> {
+        let mut client = ServiceClient::new(self.channel());
+        let resp = client
+            .get_tx(GetTxRequest {
+                hash: tx_hash.to_string(),
+            })
+            .await?;
+        let tx_response = resp
+            .get_ref()
+            .clone()
+            .tx_response
+            .ok_or(ErrorReport::msg("Empty tx_response in response"))?;
+        DecodedTx::from_tx_response(&tx_response)
+    }
+
+    pub async fn search_txs(&self, search: &TxSearch) -> Result<TxSearchPage> {
+        let request = search.request()?;
+        let mut client = Grpc::new(self.channel());
+        client
+            .ready()
+            .await
+            .map_err(|e| ErrorReport::msg(format!("Service was not ready: {}", e)))?;
+        let resp = client
+            .unary::<_, GetTxsEventResponse, _>(
+                Request::new(request),
+                PathAndQuery::from_static(GET_TXS_EVENT_PATH),
+                ProstCodec::default(),
+            )
+            .await?;
+        let resp = resp.into_inner();
+        Ok(TxSearchPage {
+            txs: resp
+                .tx_responses
+                .iter()
+                .map(DecodedTx::from_tx_response)
+                .collect::<Result<_>>()?,
+            total: resp.total,
+            page: search.page,
+            limit: search.limit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderBy, QueryClient, TxSearch};
+    use crate::{retry::RetryPolicy, types::MsgSubmitProof, wallet::Wallet};
+
+    const NODE: &str = "http://54.65.75.57:9090";
+    const UNREACHABLE_NODE: &str = "http://127.0.0.1:1";
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+
+    #[test]
+    fn build_query() {
+        let sender = Wallet::new(PRIVATE_KEY).account_id;
+        let search = TxSearch::new()
+            .with_msg_type::<MsgSubmitProof>()
+            .with_sender(&sender)
+            .with_namespace("test-namespace")
+            .with_min_height(10)
+            .with_max_height(20)
+            .with_order_by(OrderBy::Desc)
+            .with_limit(10);
+        assert_eq!(
+            search.query(),
+            format!(
+                "message.action='/fiamma.zkpverify.MsgSubmitProof' AND message.sender='{}' \
+                 AND submit_proof.namespace='test-namespace' AND tx.height>=10 AND tx.height<=20",
+                sender
+            )
+        );
+        let request = search.next_page().request().unwrap();
+        assert_eq!((request.page, request.limit), (2, 10));
+        assert_eq!(request.order_by, OrderBy::Desc as i32);
+        assert!(TxSearch::new().request().is_err());
+        assert!(search.clone().with_limit(0).request().is_err());
+        assert!(search.with_namespace("it's").request().is_err());
+    }
+
+    #[tokio::test]
+    async fn search_unreachable_node() {
+        let client = QueryClient::new(UNREACHABLE_NODE).with_retry_policy(RetryPolicy::none());
+        let search = TxSearch::new().with_proof_id("00");
+        assert!(client.search_txs(&search).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_submit_proofs() {
+        let client = QueryClient::new(NODE);
+        let sender = Wallet::new(PRIVATE_KEY).account_id;
+        let page = client
+            .search_txs(
+                &TxSearch::new()
+                    .with_msg_type::<MsgSubmitProof>()
+                    .with_sender(&sender),
+            )
+            .await;
+        println!("submit proof txs: {:?}", page);
+    }
+}
@@ -4,11 +4,26 @@ pub mod msg_remove_staker;
 pub mod msg_remove_vk;
 pub mod msg_submit_community_verification;
 pub mod msg_submit_proof;
+pub mod msg_update_committee_address;
+pub mod msg_update_da_submission_results;
+pub mod msg_update_da_submitter;
 
-pub use super::generated::fiamma::zkpverify::BitVmChallengeData;
+pub use super::generated::fiamma::bitvmstaker::{
+    MsgUpdateParams as BitvmstakerMsgUpdateParams, Params as BitvmstakerParams,
+};
+pub use super::generated::fiamma::zkpverify::{
+    BitVmChallengeData, DaSubmissionData, DaSubmissionResult, DataLocation, ProofData, ProofSystem,
+    VerificationStatus, VerifyResult,
+};
+pub use super::generated::fiamma::zkpverify::{
+    MsgUpdateParams as ZkpverifyMsgUpdateParams, Params as ZkpverifyParams,
+};
 pub use msg_create_staker::MsgCreateStaker;
 pub use msg_register_vk::MsgRegisterVK;
 pub use msg_remove_staker::MsgRemoveStaker;
 pub use msg_remove_vk::MsgRemoveVK;
 pub use msg_submit_community_verification::MsgSubmitCommunityVerification;
 pub use msg_submit_proof::MsgSubmitProof;
+pub use msg_update_committee_address::MsgUpdateCommitteeAddress;
+pub use msg_update_da_submission_results::MsgUpdateDASubmissionResults;
+pub use msg_update_da_submitter::MsgUpdateDASubmitter;
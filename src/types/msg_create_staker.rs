@@ -1,7 +1,10 @@
+use crate::amino::{self, AminoMsg};
 use crate::generated::fiamma::bitvmstaker::MsgCreateStaker as ProtoMsgCreateStaker;
 use cosmrs::{tx::Msg, AccountId, ErrorReport, Result};
+use serde_json::{Map, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MsgCreateStaker {
     pub creator: AccountId,
     pub staker_address: String,
@@ -11,6 +14,15 @@ impl Msg for MsgCreateStaker {
     type Proto = ProtoMsgCreateStaker;
 }
 
+impl AminoMsg for MsgCreateStaker {
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        amino::insert_str(&mut value, "creator", self.creator.as_ref());
+        amino::insert_str(&mut value, "staker_address", &self.staker_address);
+        value
+    }
+}
+
 impl TryFrom<ProtoMsgCreateStaker> for MsgCreateStaker {
     type Error = ErrorReport;
 
@@ -1,9 +1,13 @@
+use crate::amino::{self, AminoMsg};
 use crate::generated::fiamma::bitvmstaker::MsgRegisterVk as ProtoMsgRegisterVK;
 use cosmrs::{tx::Msg, AccountId, ErrorReport, Result};
+use serde_json::{Map, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MsgRegisterVK {
     pub creator: AccountId,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub vk: Vec<u8>,
 }
 
@@ -11,6 +15,15 @@ impl Msg for MsgRegisterVK {
     type Proto = ProtoMsgRegisterVK;
 }
 
+impl AminoMsg for MsgRegisterVK {
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        amino::insert_str(&mut value, "creator", self.creator.as_ref());
+        amino::insert_bytes(&mut value, "vk", &self.vk);
+        value
+    }
+}
+
 impl TryFrom<ProtoMsgRegisterVK> for MsgRegisterVK {
     type Error = ErrorReport;
 
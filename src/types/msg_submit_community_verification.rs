@@ -1,7 +1,10 @@
+use crate::amino::{self, AminoMsg};
 use crate::generated::fiamma::zkpverify::MsgSubmitCommunityVerification as ProtoMsgSubmitCommunityVerification;
 use cosmrs::{tx::Msg, AccountId, ErrorReport, Result};
+use serde_json::{Map, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MsgSubmitCommunityVerification {
     pub creator: AccountId,
     pub proof_id: String,
@@ -12,6 +15,16 @@ impl Msg for MsgSubmitCommunityVerification {
     type Proto = ProtoMsgSubmitCommunityVerification;
 }
 
+impl AminoMsg for MsgSubmitCommunityVerification {
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        amino::insert_str(&mut value, "creator", self.creator.as_ref());
+        amino::insert_str(&mut value, "proof_id", &self.proof_id);
+        amino::insert_bool(&mut value, "verify_result", self.verify_result);
+        value
+    }
+}
+
 impl TryFrom<ProtoMsgSubmitCommunityVerification> for MsgSubmitCommunityVerification {
     type Error = ErrorReport;
 
@@ -31,7 +44,7 @@ impl TryFrom<&ProtoMsgSubmitCommunityVerification> for MsgSubmitCommunityVerific
         Ok(MsgSubmitCommunityVerification {
             creator: proto.creator.parse()?,
             proof_id: proto.proof_id.parse()?,
-            verify_result: proto.verify_result.clone(),
+            verify_result: proto.verify_result,
         })
     }
 }
@@ -47,7 +60,7 @@ impl From<&MsgSubmitCommunityVerification> for ProtoMsgSubmitCommunityVerificati
         ProtoMsgSubmitCommunityVerification {
             creator: msg.creator.to_string(),
             proof_id: msg.proof_id.to_string(),
-            verify_result: msg.verify_result.clone(),
+            verify_result: msg.verify_result,
         }
     }
 }
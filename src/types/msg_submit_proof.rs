@@ -1,12 +1,19 @@
+use crate::amino::{self, AminoMsg};
 use crate::generated::fiamma::zkpverify::MsgSubmitProof as ProtoMsgSubmitProof;
 use cosmrs::{tx::Msg, AccountId, ErrorReport, Result};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MsgSubmitProof {
     pub creator: AccountId,
     pub proof_system: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub proof: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub public_input: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::hex_bytes"))]
     pub vk: Vec<u8>,
     pub namespace: String,
     pub data_location: String,
@@ -16,6 +23,52 @@ impl Msg for MsgSubmitProof {
     type Proto = ProtoMsgSubmitProof;
 }
 
+impl MsgSubmitProof {
+    /// The ID the chain assigns this proof, see [`hash_proof_id`].
+    pub fn proof_id(&self) -> String {
+        hash_proof_id(
+            &self.namespace,
+            &self.proof_system,
+            &self.proof,
+            &self.public_input,
+            &self.vk,
+        )
+    }
+}
+
+/// The chain's proof ID: hex `sha256(namespace || proof_system || proof || public_input || vk)`,
+/// with the proof system as its protobuf name.
+pub(crate) fn hash_proof_id(
+    namespace: &str,
+    proof_system: &str,
+    proof: &[u8],
+    public_input: &[u8],
+    vk: &[u8],
+) -> String {
+    let hash = Sha256::new()
+        .chain_update(namespace)
+        .chain_update(proof_system)
+        .chain_update(proof)
+        .chain_update(public_input)
+        .chain_update(vk)
+        .finalize();
+    hex::encode(hash)
+}
+
+impl AminoMsg for MsgSubmitProof {
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        amino::insert_str(&mut value, "creator", self.creator.as_ref());
+        amino::insert_str(&mut value, "proof_system", &self.proof_system);
+        amino::insert_bytes(&mut value, "proof", &self.proof);
+        amino::insert_bytes(&mut value, "public_input", &self.public_input);
+        amino::insert_bytes(&mut value, "vk", &self.vk);
+        amino::insert_str(&mut value, "data_location", &self.data_location);
+        amino::insert_str(&mut value, "namespace", &self.namespace);
+        value
+    }
+}
+
 impl TryFrom<ProtoMsgSubmitProof> for MsgSubmitProof {
     type Error = ErrorReport;
 
@@ -0,0 +1,62 @@
+use crate::amino::{self, AminoMsg};
+use crate::generated::fiamma::bitvmstaker::MsgUpdateCommitteeAddress as ProtoMsgUpdateCommitteeAddress;
+use cosmrs::{tx::Msg, AccountId, ErrorReport, Result};
+use serde_json::{Map, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsgUpdateCommitteeAddress {
+    pub creator: AccountId,
+    pub new_committee_address: String,
+}
+
+impl Msg for MsgUpdateCommitteeAddress {
+    type Proto = ProtoMsgUpdateCommitteeAddress;
+}
+
+impl AminoMsg for MsgUpdateCommitteeAddress {
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        amino::insert_str(&mut value, "creator", self.creator.as_ref());
+        amino::insert_str(
+            &mut value,
+            "new_committee_address",
+            &self.new_committee_address,
+        );
+        value
+    }
+}
+
+impl TryFrom<ProtoMsgUpdateCommitteeAddress> for MsgUpdateCommitteeAddress {
+    type Error = ErrorReport;
+
+    fn try_from(proto: ProtoMsgUpdateCommitteeAddress) -> Result<MsgUpdateCommitteeAddress> {
+        MsgUpdateCommitteeAddress::try_from(&proto)
+    }
+}
+
+impl TryFrom<&ProtoMsgUpdateCommitteeAddress> for MsgUpdateCommitteeAddress {
+    type Error = ErrorReport;
+
+    fn try_from(proto: &ProtoMsgUpdateCommitteeAddress) -> Result<MsgUpdateCommitteeAddress> {
+        Ok(MsgUpdateCommitteeAddress {
+            creator: proto.creator.parse()?,
+            new_committee_address: proto.new_committee_address.parse()?,
+        })
+    }
+}
+
+impl From<MsgUpdateCommitteeAddress> for ProtoMsgUpdateCommitteeAddress {
+    fn from(coin: MsgUpdateCommitteeAddress) -> ProtoMsgUpdateCommitteeAddress {
+        ProtoMsgUpdateCommitteeAddress::from(&coin)
+    }
+}
+
+impl From<&MsgUpdateCommitteeAddress> for ProtoMsgUpdateCommitteeAddress {
+    fn from(msg: &MsgUpdateCommitteeAddress) -> ProtoMsgUpdateCommitteeAddress {
+        ProtoMsgUpdateCommitteeAddress {
+            creator: msg.creator.to_string(),
+            new_committee_address: msg.new_committee_address.to_string(),
+        }
+    }
+}
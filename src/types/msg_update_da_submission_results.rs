@@ -0,0 +1,76 @@
+use crate::amino::{self, AminoMsg};
+use crate::generated::fiamma::zkpverify::{
+    DaSubmissionResult, MsgUpdateDaSubmissionResults as ProtoMsgUpdateDASubmissionResults,
+};
+use cosmrs::{tx::Msg, AccountId, ErrorReport, Result};
+use serde_json::{Map, Value};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsgUpdateDASubmissionResults {
+    pub creator: AccountId,
+    pub da_submission_results: Vec<DaSubmissionResult>,
+}
+
+impl Msg for MsgUpdateDASubmissionResults {
+    type Proto = ProtoMsgUpdateDASubmissionResults;
+}
+
+impl AminoMsg for MsgUpdateDASubmissionResults {
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        amino::insert_str(&mut value, "creator", self.creator.as_ref());
+        if !self.da_submission_results.is_empty() {
+            let results = self
+                .da_submission_results
+                .iter()
+                .map(|result| {
+                    let mut value = Map::new();
+                    amino::insert_str(&mut value, "proof_id", &result.proof_id);
+                    amino::insert_i32(&mut value, "data_location", result.data_location);
+                    amino::insert_str(&mut value, "block_hash", &result.block_hash);
+                    amino::insert_u64(&mut value, "block_height", result.block_height);
+                    amino::insert_str(&mut value, "tx_hash", &result.tx_hash);
+                    amino::insert_str(&mut value, "namespace", &result.namespace);
+                    Value::Object(value)
+                })
+                .collect();
+            value.insert("da_submission_result".to_string(), results);
+        }
+        value
+    }
+}
+
+impl TryFrom<ProtoMsgUpdateDASubmissionResults> for MsgUpdateDASubmissionResults {
+    type Error = ErrorReport;
+
+    fn try_from(proto: ProtoMsgUpdateDASubmissionResults) -> Result<MsgUpdateDASubmissionResults> {
+        MsgUpdateDASubmissionResults::try_from(&proto)
+    }
+}
+
+impl TryFrom<&ProtoMsgUpdateDASubmissionResults> for MsgUpdateDASubmissionResults {
+    type Error = ErrorReport;
+
+    fn try_from(proto: &ProtoMsgUpdateDASubmissionResults) -> Result<MsgUpdateDASubmissionResults> {
+        Ok(MsgUpdateDASubmissionResults {
+            creator: proto.creator.parse()?,
+            da_submission_results: proto.da_submission_result.clone(),
+        })
+    }
+}
+
+impl From<MsgUpdateDASubmissionResults> for ProtoMsgUpdateDASubmissionResults {
+    fn from(msg: MsgUpdateDASubmissionResults) -> ProtoMsgUpdateDASubmissionResults {
+        ProtoMsgUpdateDASubmissionResults::from(&msg)
+    }
+}
+
+impl From<&MsgUpdateDASubmissionResults> for ProtoMsgUpdateDASubmissionResults {
+    fn from(msg: &MsgUpdateDASubmissionResults) -> ProtoMsgUpdateDASubmissionResults {
+        ProtoMsgUpdateDASubmissionResults {
+            creator: msg.creator.to_string(),
+            da_submission_result: msg.da_submission_results.clone(),
+        }
+    }
+}
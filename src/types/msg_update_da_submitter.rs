@@ -0,0 +1,58 @@
+use crate::amino::{self, AminoMsg};
+use crate::generated::fiamma::zkpverify::MsgUpdateDaSubmitter as ProtoMsgUpdateDASubmitter;
+use cosmrs::{tx::Msg, AccountId, ErrorReport, Result};
+use serde_json::{Map, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsgUpdateDASubmitter {
+    pub creator: AccountId,
+    pub da_submitter: String,
+}
+
+impl Msg for MsgUpdateDASubmitter {
+    type Proto = ProtoMsgUpdateDASubmitter;
+}
+
+impl AminoMsg for MsgUpdateDASubmitter {
+    fn amino_value(&self) -> Map<String, Value> {
+        let mut value = Map::new();
+        amino::insert_str(&mut value, "creator", self.creator.as_ref());
+        amino::insert_str(&mut value, "da_submitter", &self.da_submitter);
+        value
+    }
+}
+
+impl TryFrom<ProtoMsgUpdateDASubmitter> for MsgUpdateDASubmitter {
+    type Error = ErrorReport;
+
+    fn try_from(proto: ProtoMsgUpdateDASubmitter) -> Result<MsgUpdateDASubmitter> {
+        MsgUpdateDASubmitter::try_from(&proto)
+    }
+}
+
+impl TryFrom<&ProtoMsgUpdateDASubmitter> for MsgUpdateDASubmitter {
+    type Error = ErrorReport;
+
+    fn try_from(proto: &ProtoMsgUpdateDASubmitter) -> Result<MsgUpdateDASubmitter> {
+        Ok(MsgUpdateDASubmitter {
+            creator: proto.creator.parse()?,
+            da_submitter: proto.da_submitter.parse()?,
+        })
+    }
+}
+
+impl From<MsgUpdateDASubmitter> for ProtoMsgUpdateDASubmitter {
+    fn from(coin: MsgUpdateDASubmitter) -> ProtoMsgUpdateDASubmitter {
+        ProtoMsgUpdateDASubmitter::from(&coin)
+    }
+}
+
+impl From<&MsgUpdateDASubmitter> for ProtoMsgUpdateDASubmitter {
+    fn from(msg: &MsgUpdateDASubmitter) -> ProtoMsgUpdateDASubmitter {
+        ProtoMsgUpdateDASubmitter {
+            creator: msg.creator.to_string(),
+            da_submitter: msg.da_submitter.to_string(),
+        }
+    }
+}
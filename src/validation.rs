@@ -0,0 +1,490 @@
+use crate::types::{DataLocation, MsgSubmitProof, ProofSystem};
+use cosmrs::AccountId;
+use std::fmt;
+
+pub const MAX_NAMESPACE_LEN: usize = 64;
+/// Bech32 prefix of staker (validator operator) addresses.
+pub const VALOPER_PREFIX: &str = "fiammavaloper";
+
+pub const G1_COMPRESSED_SIZE: usize = 32;
+pub const G2_COMPRESSED_SIZE: usize = 64;
+pub const FR_SIZE: usize = 32;
+pub(crate) const LENGTH_PREFIX_SIZE: usize = 8;
+
+/// Groth16 proof: compressed `a: G1`, `b: G2`, `c: G1`.
+pub const GROTH16_PROOF_SIZE: usize = 2 * G1_COMPRESSED_SIZE + G2_COMPRESSED_SIZE;
+/// Fixed part of a Groth16 VK: `alpha: G1`, `beta, gamma, delta: G2`, then the `u64` length
+/// of `gamma_abc: Vec<G1>`.
+pub const GROTH16_VK_FIXED_SIZE: usize =
+    G1_COMPRESSED_SIZE + 3 * G2_COMPRESSED_SIZE + LENGTH_PREFIX_SIZE;
+/// FFLONK proof: compressed `c1, c2, w1, w2: G1` followed by 16 `Fr` evaluations in snarkjs
+/// order (`ql, qr, qm, qo, qc, s1, s2, s3, a, b, c, z, zw, t1w, t2w, inv`).
+pub const FFLONK_PROOF_SIZE: usize = 4 * G1_COMPRESSED_SIZE + 16 * FR_SIZE;
+/// FFLONK VK, the fields of snarkjs' `verification_key.json` in order: `n_public: u64`,
+/// `power: u64`, `k1, k2, w, w3, w4, w8, wr: Fr`, compressed `x_2: G2` and `c0: G1`.
+///
+/// This layout is derived from snarkjs' output only. It has not been checked against the
+/// chain's FFLONK verifier, which may expect a different encoding, so
+/// [`MsgSubmitProof::validate`] does not apply it.
+pub const FFLONK_VK_SIZE: usize = 2 * 8 + 7 * FR_SIZE + G2_COMPRESSED_SIZE + G1_COMPRESSED_SIZE;
+
+// BN254 base field modulus p and scalar field modulus r, little endian.
+pub(crate) const FQ_MODULUS: [u8; 32] = [
+    0x47, 0xfd, 0x7c, 0xd8, 0x16, 0x8c, 0x20, 0x3c, 0x8d, 0xca, 0x71, 0x68, 0x91, 0x6a, 0x81, 0x97,
+    0x5d, 0x58, 0x81, 0x81, 0xb6, 0x45, 0x50, 0xb8, 0x29, 0xa0, 0x31, 0xe1, 0x72, 0x4e, 0x64, 0x30,
+];
+pub(crate) const FR_MODULUS: [u8; 32] = [
+    0x01, 0x00, 0x00, 0xf0, 0x93, 0xf5, 0xe1, 0x43, 0x91, 0x70, 0xb9, 0x79, 0x48, 0xe8, 0x33, 0x28,
+    0x5d, 0x58, 0x81, 0x81, 0xb6, 0x45, 0x50, 0xb8, 0x29, 0xa0, 0x31, 0xe1, 0x72, 0x4e, 0x64, 0x30,
+];
+// arkworks stores the point-at-infinity and y-sign flags in the top two bits of the last byte.
+pub(crate) const FLAGS_MASK: u8 = 0b1100_0000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    UnknownProofSystem(String),
+    UnknownDataLocation(String),
+    InvalidNamespace {
+        namespace: String,
+        reason: String,
+    },
+    InvalidLength {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    InvalidElement {
+        field: &'static str,
+        index: usize,
+    },
+    PublicInputCountMismatch {
+        vk: usize,
+        public_input: usize,
+    },
+    UnregisteredVk,
+    InvalidStakerAddress(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::UnknownProofSystem(proof_system) => {
+                write!(f, "unknown proof system {}", proof_system)
+            }
+            ValidationError::UnknownDataLocation(data_location) => {
+                write!(f, "unknown data location {}", data_location)
+            }
+            ValidationError::InvalidNamespace { namespace, reason } => {
+                write!(f, "invalid namespace {:?}: {}", namespace, reason)
+            }
+            ValidationError::InvalidLength {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid {} length: expected {} bytes, got {}",
+                field, expected, actual
+            ),
+            ValidationError::InvalidElement { field, index } => {
+                write!(f, "{} element {} is not a canonical encoding", field, index)
+            }
+            ValidationError::PublicInputCountMismatch { vk, public_input } => {
+                write!(f, "vk expects {} public inputs, got {}", vk, public_input)
+            }
+            ValidationError::UnregisteredVk => write!(f, "vk is not in the registered vk list"),
+            ValidationError::InvalidStakerAddress(address) => write!(
+                f,
+                "invalid staker address {:?}: expected a {} bech32 address",
+                address, VALOPER_PREFIX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl MsgSubmitProof {
+    /// Checks the message locally so malformed submissions fail before paying gas.
+    ///
+    /// Besides the Groth16 proof, VK and public input encodings, this applies the SDK's namespace
+    /// convention from [`validate_namespace`], which the chain itself does not enforce.
+    /// [`crate::tx::TxClient`] only runs it when enabled with `with_proof_validation`. It
+    /// does not check that the VK is registered; use
+    /// [`crate::tx::TxClient::validate_proof`] for that.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_namespace(&self.namespace)?;
+        // An empty data location is stored as the enum's zero value, `FIAMMA`.
+        if !self.data_location.is_empty() {
+            DataLocation::from_str_name(&self.data_location).ok_or(
+                ValidationError::UnknownDataLocation(self.data_location.clone()),
+            )?;
+        }
+        let proof_system = ProofSystem::from_str_name(&self.proof_system).ok_or(
+            ValidationError::UnknownProofSystem(self.proof_system.clone()),
+        )?;
+        // The chain's FFLONK encoding is not known here, so FFLONK bytes are left to it.
+        if proof_system == ProofSystem::FfplonkBn254Bitvm {
+            return Ok(());
+        }
+        let public_inputs = validate_public_input(&self.public_input)?;
+        validate_groth16_proof(&self.proof)?;
+        let vk_inputs = validate_groth16_vk(&self.vk)?;
+        if vk_inputs != public_inputs {
+            return Err(ValidationError::PublicInputCountMismatch {
+                vk: vk_inputs,
+                public_input: public_inputs,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The SDK's namespace convention: 1 to [`MAX_NAMESPACE_LEN`] ASCII letters, digits, `-` and
+/// `_`. The chain accepts any string, so this only keeps namespaces portable across tools.
+pub fn validate_namespace(namespace: &str) -> Result<(), ValidationError> {
+    let invalid = |reason: String| ValidationError::InvalidNamespace {
+        namespace: namespace.to_string(),
+        reason,
+    };
+    if namespace.is_empty() {
+        return Err(invalid("namespace is empty".to_string()));
+    }
+    if namespace.len() > MAX_NAMESPACE_LEN {
+        return Err(invalid(format!(
+            "namespace is longer than {} characters",
+            MAX_NAMESPACE_LEN
+        )));
+    }
+    if !namespace
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(invalid(
+            "namespace may only contain ASCII letters, digits, '-' and '_'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `address` is a well-formed `fiammavaloper` bech32 address.
+pub fn validate_staker_address(address: &str) -> Result<(), ValidationError> {
+    match address.parse::<AccountId>() {
+        Ok(account_id) if account_id.prefix() == VALOPER_PREFIX => Ok(()),
+        _ => Err(ValidationError::InvalidStakerAddress(address.to_string())),
+    }
+}
+
+/// Public inputs are a `u64` little endian count followed by that many `Fr` elements.
+/// Returns the number of public inputs.
+pub fn validate_public_input(public_input: &[u8]) -> Result<usize, ValidationError> {
+    let count = read_length_prefix("public_input", public_input, 0)?;
+    let expected = LENGTH_PREFIX_SIZE + count * FR_SIZE;
+    check_length("public_input", expected, public_input.len())?;
+    for (index, fr) in public_input[LENGTH_PREFIX_SIZE..]
+        .chunks(FR_SIZE)
+        .enumerate()
+    {
+        if !is_canonical_fr(fr) {
+            return Err(ValidationError::InvalidElement {
+                field: "public_input",
+                index,
+            });
+        }
+    }
+    Ok(count)
+}
+
+pub fn validate_groth16_proof(proof: &[u8]) -> Result<(), ValidationError> {
+    check_length("proof", GROTH16_PROOF_SIZE, proof.len())?;
+    check_g1("proof", 0, &proof[..32])?;
+    check_g2("proof", 1, &proof[32..96])?;
+    check_g1("proof", 2, &proof[96..])
+}
+
+/// Returns the number of public inputs the VK expects.
+pub fn validate_groth16_vk(vk: &[u8]) -> Result<usize, ValidationError> {
+    if vk.len() < GROTH16_VK_FIXED_SIZE {
+        return Err(ValidationError::InvalidLength {
+            field: "vk",
+            expected: GROTH16_VK_FIXED_SIZE,
+            actual: vk.len(),
+        });
+    }
+    check_g1("vk", 0, &vk[..32])?;
+    for (index, g2) in vk[32..224].chunks(G2_COMPRESSED_SIZE).enumerate() {
+        check_g2("vk", index + 1, g2)?;
+    }
+    let gamma_abc_len = read_length_prefix("vk", vk, 224)?;
+    if gamma_abc_len == 0 {
+        return Err(ValidationError::InvalidElement {
+            field: "vk",
+            index: 4,
+        });
+    }
+    check_length(
+        "vk",
+        GROTH16_VK_FIXED_SIZE + gamma_abc_len * G1_COMPRESSED_SIZE,
+        vk.len(),
+    )?;
+    for (index, g1) in vk[GROTH16_VK_FIXED_SIZE..]
+        .chunks(G1_COMPRESSED_SIZE)
+        .enumerate()
+    {
+        check_g1("vk", index + 4, g1)?;
+    }
+    Ok(gamma_abc_len - 1)
+}
+
+/// Checks a proof in the [`FFLONK_PROOF_SIZE`] layout of the snarkjs converter. Passing does
+/// not mean the chain can read the proof.
+pub fn validate_fflonk_proof(proof: &[u8]) -> Result<(), ValidationError> {
+    check_length("proof", FFLONK_PROOF_SIZE, proof.len())?;
+    for (index, g1) in proof[..4 * G1_COMPRESSED_SIZE]
+        .chunks(G1_COMPRESSED_SIZE)
+        .enumerate()
+    {
+        check_g1("proof", index, g1)?;
+    }
+    for (index, fr) in proof[4 * G1_COMPRESSED_SIZE..].chunks(FR_SIZE).enumerate() {
+        if !is_canonical_fr(fr) {
+            return Err(ValidationError::InvalidElement {
+                field: "proof",
+                index: index + 4,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks a VK in the [`FFLONK_VK_SIZE`] layout and returns the number of public inputs it
+/// expects. Passing does not guarantee the chain accepts the VK.
+pub fn validate_fflonk_vk(vk: &[u8]) -> Result<usize, ValidationError> {
+    check_length("vk", FFLONK_VK_SIZE, vk.len())?;
+    let n_public = u64::from_le_bytes(vk[..8].try_into().expect("n_public is 8 bytes"));
+    for (index, fr) in vk[16..16 + 7 * FR_SIZE].chunks(FR_SIZE).enumerate() {
+        if !is_canonical_fr(fr) {
+            return Err(ValidationError::InvalidElement {
+                field: "vk",
+                index: index + 2,
+            });
+        }
+    }
+    let x_2 = 16 + 7 * FR_SIZE;
+    check_g2("vk", 9, &vk[x_2..x_2 + G2_COMPRESSED_SIZE])?;
+    check_g1("vk", 10, &vk[x_2 + G2_COMPRESSED_SIZE..])?;
+    Ok(usize::try_from(n_public).unwrap_or(usize::MAX))
+}
+
+fn check_length(
+    field: &'static str,
+    expected: usize,
+    actual: usize,
+) -> Result<(), ValidationError> {
+    if expected != actual {
+        return Err(ValidationError::InvalidLength {
+            field,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn read_length_prefix(
+    field: &'static str,
+    bytes: &[u8],
+    offset: usize,
+) -> Result<usize, ValidationError> {
+    let prefix =
+        bytes
+            .get(offset..offset + LENGTH_PREFIX_SIZE)
+            .ok_or(ValidationError::InvalidLength {
+                field,
+                expected: offset + LENGTH_PREFIX_SIZE,
+                actual: bytes.len(),
+            })?;
+    let len = u64::from_le_bytes(prefix.try_into().expect("prefix is 8 bytes"));
+    // A count larger than the buffer can never match, so clamp it to keep the caller's
+    // length arithmetic from overflowing; the caller's length check then rejects it.
+    Ok(usize::try_from(len).unwrap_or(usize::MAX).min(bytes.len()))
+}
+
+fn check_g1(field: &'static str, index: usize, g1: &[u8]) -> Result<(), ValidationError> {
+    if is_canonical_fq(g1, true) {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidElement { field, index })
+    }
+}
+
+fn check_g2(field: &'static str, index: usize, g2: &[u8]) -> Result<(), ValidationError> {
+    if is_canonical_fq(&g2[..32], false) && is_canonical_fq(&g2[32..], true) {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidElement { field, index })
+    }
+}
+
+fn is_canonical_fq(bytes: &[u8], flagged: bool) -> bool {
+    let mut element: [u8; 32] = bytes.try_into().expect("field element is 32 bytes");
+    if flagged {
+        element[31] &= !FLAGS_MASK;
+    }
+    less_than_le(&element, &FQ_MODULUS)
+}
+
+fn is_canonical_fr(bytes: &[u8]) -> bool {
+    less_than_le(bytes, &FR_MODULUS)
+}
+
+pub(crate) fn less_than_le(a: &[u8], b: &[u8; 32]) -> bool {
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        if x != y {
+            return x < y;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        validate_fflonk_proof, validate_fflonk_vk, ValidationError, FFLONK_PROOF_SIZE,
+        FFLONK_VK_SIZE,
+    };
+    use crate::{types::MsgSubmitProof, wallet::Wallet};
+
+    const TEST_DATA: &str = "test-data";
+    const PRIVATE_KEY: &str = "7ae58f95b0f15c999f77488fa0fbebbd4acbe2d12948dcd1729b07ee8f3051e8";
+
+    fn msg_submit_proof() -> MsgSubmitProof {
+        let location = std::env::current_dir().unwrap().join(TEST_DATA);
+        MsgSubmitProof {
+            creator: Wallet::new(PRIVATE_KEY).account_id,
+            proof_system: "GROTH16_BN254_BITVM".to_string(),
+            proof: std::fs::read(location.join("proof.bitvm")).unwrap(),
+            public_input: std::fs::read(location.join("public_input.bitvm")).unwrap(),
+            vk: std::fs::read(location.join("vk.bitvm")).unwrap(),
+            namespace: "test-namespace".to_string(),
+            data_location: "FIAMMA".to_string(),
+        }
+    }
+
+    #[test]
+    fn valid_groth16_submission() {
+        assert_eq!(msg_submit_proof().validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_enums_and_namespace() {
+        let mut msg = msg_submit_proof();
+        msg.proof_system = "PLONK".to_string();
+        assert_eq!(
+            msg.validate(),
+            Err(ValidationError::UnknownProofSystem("PLONK".to_string()))
+        );
+
+        let mut msg = msg_submit_proof();
+        msg.data_location = "IPFS".to_string();
+        assert_eq!(
+            msg.validate(),
+            Err(ValidationError::UnknownDataLocation("IPFS".to_string()))
+        );
+        msg.data_location = String::new();
+        assert_eq!(msg.validate(), Ok(()));
+
+        for namespace in ["", "has space", "ünïcode", &"a".repeat(65)] {
+            let mut msg = msg_submit_proof();
+            msg.namespace = namespace.to_string();
+            assert!(matches!(
+                msg.validate(),
+                Err(ValidationError::InvalidNamespace { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_groth16_artifacts() {
+        let mut msg = msg_submit_proof();
+        msg.proof.pop();
+        assert_eq!(
+            msg.validate(),
+            Err(ValidationError::InvalidLength {
+                field: "proof",
+                expected: 128,
+                actual: 127
+            })
+        );
+
+        let mut msg = msg_submit_proof();
+        msg.proof[..32].copy_from_slice(&[0xff; 32]);
+        msg.proof[31] = 0x3f;
+        assert_eq!(
+            msg.validate(),
+            Err(ValidationError::InvalidElement {
+                field: "proof",
+                index: 0
+            })
+        );
+
+        let mut msg = msg_submit_proof();
+        msg.public_input[0] = 2;
+        assert_eq!(
+            msg.validate(),
+            Err(ValidationError::InvalidLength {
+                field: "public_input",
+                expected: 72,
+                actual: 40
+            })
+        );
+
+        let mut msg = msg_submit_proof();
+        msg.public_input = vec![0; 8];
+        assert_eq!(
+            msg.validate(),
+            Err(ValidationError::PublicInputCountMismatch {
+                vk: 1,
+                public_input: 0
+            })
+        );
+
+        let mut msg = msg_submit_proof();
+        msg.vk.truncate(264);
+        assert!(matches!(
+            msg.validate(),
+            Err(ValidationError::InvalidLength { field: "vk", .. })
+        ));
+    }
+
+    #[test]
+    fn fflonk_bytes_are_not_checked() {
+        let mut msg = msg_submit_proof();
+        msg.proof_system = "FFPLONK_BN254_BITVM".to_string();
+        msg.proof.truncate(100);
+        assert_eq!(msg.validate(), Ok(()));
+        msg.namespace = String::new();
+        assert!(msg.validate().is_err());
+    }
+
+    #[test]
+    fn fflonk_snarkjs_layout() {
+        assert!(matches!(
+            validate_fflonk_proof(&[0; 128]),
+            Err(ValidationError::InvalidLength { field: "proof", .. })
+        ));
+        assert_eq!(validate_fflonk_proof(&[0; FFLONK_PROOF_SIZE]), Ok(()));
+
+        let mut vk = vec![0; FFLONK_VK_SIZE];
+        vk[0] = 2;
+        assert_eq!(validate_fflonk_vk(&vk), Ok(2));
+        vk[16..48].fill(0xff);
+        assert_eq!(
+            validate_fflonk_vk(&vk),
+            Err(ValidationError::InvalidElement {
+                field: "vk",
+                index: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{
+    types::{MsgRegisterVK, MsgRemoveVK},
+    validation::{validate_fflonk_vk, validate_groth16_vk, ValidationError, FFLONK_VK_SIZE},
+};
+use cosmrs::{tx::Msg, AccountId, Any, ErrorReport, Result};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// Extension of VK files written by [`VkRegistry::export_to_dir`].
+pub const VK_FILE_EXTENSION: &str = "vk";
+
+/// A verification key, identified by the hex SHA-256 of its bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vk {
+    id: String,
+    bytes: Vec<u8>,
+}
+
+impl Vk {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            id: vk_id(&bytes),
+            bytes,
+        }
+    }
+
+    /// Loads a VK file holding either the raw serialized VK or its hex encoding.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read(path).map_err(|e| {
+            ErrorReport::msg(format!("Failed to read vk {}: {}", path.display(), e))
+        })?;
+        let hex_vk = std::str::from_utf8(&contents)
+            .ok()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .and_then(|text| hex::decode(text.trim_start_matches("0x")).ok());
+        Ok(Self::new(hex_vk.unwrap_or(contents)))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Parses the VK header. BN254 Groth16 and FFLONK VKs have distinct sizes, so the proof
+    /// system is told apart by the size.
+    pub fn metadata(&self) -> Result<VkMetadata, ValidationError> {
+        let (proof_system, num_public_inputs) = if self.bytes.len() == FFLONK_VK_SIZE {
+            ("FFPLONK_BN254_BITVM", validate_fflonk_vk(&self.bytes)?)
+        } else {
+            ("GROTH16_BN254_BITVM", validate_groth16_vk(&self.bytes)?)
+        };
+        Ok(VkMetadata {
+            proof_system,
+            curve: "BN254",
+            num_public_inputs,
+            size: self.bytes.len(),
+        })
+    }
+}
+
+/// The stable ID of a VK: hex `sha256(vk)`.
+pub fn vk_id(vk: &[u8]) -> String {
+    hex::encode(Sha256::digest(vk))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VkMetadata {
+    pub proof_system: &'static str,
+    pub curve: &'static str,
+    pub num_public_inputs: usize,
+    /// Serialized size in bytes.
+    pub size: usize,
+}
+
+impl fmt::Display for VkMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} over {}, {} public inputs, {} bytes",
+            self.proof_system, self.curve, self.num_public_inputs, self.size
+        )
+    }
+}
+
+/// Loads every VK file in `dir`, skipping hidden files and subdirectories.
+pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Vk>> {
+    let dir = dir.as_ref();
+    let entries = fs::read_dir(dir)
+        .map_err(|e| ErrorReport::msg(format!("Failed to read vk dir {}: {}", dir.display(), e)))?;
+    let mut paths = vec![];
+    for entry in entries {
+        let entry = entry?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if entry.file_type()?.is_file() && !hidden {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+    paths.iter().map(Vk::from_file).collect()
+}
+
+/// The VKs registered on chain, keyed by ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VkRegistry {
+    vks: BTreeMap<String, Vk>,
+}
+
+impl VkRegistry {
+    pub fn new(vks: impl IntoIterator<Item = Vk>) -> Self {
+        Self {
+            vks: vks.into_iter().map(|vk| (vk.id.clone(), vk)).collect(),
+        }
+    }
+
+    /// Builds the registry from `get_registered_vk_list`'s hex strings.
+    pub fn from_registered_vk_list(registered_vk_list: &[String]) -> Result<Self> {
+        let vks = registered_vk_list
+            .iter()
+            .map(|vk| Ok(Vk::new(hex::decode(vk)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(vks))
+    }
+
+    pub fn contains(&self, vk: &[u8]) -> bool {
+        self.contains_id(&vk_id(vk))
+    }
+
+    pub fn contains_id(&self, id: &str) -> bool {
+        self.vks.contains_key(id)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Vk> {
+        self.vks.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.vks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vks.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Vk> {
+        self.vks.values()
+    }
+
+    pub fn check_registered(&self, vk: &[u8]) -> Result<(), ValidationError> {
+        if self.contains(vk) {
+            Ok(())
+        } else {
+            Err(ValidationError::UnregisteredVk)
+        }
+    }
+
+    /// The registrations needed for the chain to hold every VK in `local`. With
+    /// `remove_unlisted`, registered VKs missing from `local` are removed as well.
+    pub fn sync_plan(&self, local: &[Vk], remove_unlisted: bool) -> VkSyncPlan {
+        let local = VkRegistry::new(local.iter().cloned());
+        let to_register = local
+            .iter()
+            .filter(|vk| !self.contains_id(&vk.id))
+            .cloned()
+            .collect();
+        let to_remove = if remove_unlisted {
+            self.iter()
+                .filter(|vk| !local.contains_id(&vk.id))
+                .cloned()
+                .collect()
+        } else {
+            vec![]
+        };
+        VkSyncPlan {
+            to_register,
+            to_remove,
+        }
+    }
+
+    /// Writes each registered VK missing from `dir` as `<id>.vk`. Returns the written paths.
+    pub fn export_to_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let existing = VkRegistry::new(load_dir(dir)?);
+        let mut written = vec![];
+        for vk in self.iter().filter(|vk| !existing.contains_id(&vk.id)) {
+            let path = dir.join(format!("{}.{}", vk.id, VK_FILE_EXTENSION));
+            fs::write(&path, &vk.bytes)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+/// VKs to register and remove, each sorted by ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VkSyncPlan {
+    pub to_register: Vec<Vk>,
+    pub to_remove: Vec<Vk>,
+}
+
+impl VkSyncPlan {
+    pub fn is_empty(&self) -> bool {
+        self.to_register.is_empty() && self.to_remove.is_empty()
+    }
+
+    /// One `MsgRemoveVK` per removed VK followed by one `MsgRegisterVK` per new VK, signed by
+    /// `creator` (the committee address).
+    pub fn msgs(&self, creator: &AccountId) -> Result<Vec<Any>> {
+        let removes = self.to_remove.iter().map(|vk| {
+            MsgRemoveVK {
+                creator: creator.clone(),
+                vk: vk.bytes.clone(),
+            }
+            .to_any()
+        });
+        let registers = self.to_register.iter().map(|vk| {
+            MsgRegisterVK {
+                creator: creator.clone(),
+                vk: vk.bytes.clone(),
+            }
+            .to_any()
+        });
+        removes.chain(registers).collect()
+    }
+}
+
+/// One line per change, `- <id> (<metadata>)` for removals and `+ ...` for registrations.
+impl fmt::Display for VkSyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no vk changes");
+        }
+        let removes = self.to_remove.iter().map(|vk| ('-', vk));
+        let registers = self.to_register.iter().map(|vk| ('+', vk));
+        for (i, (sign, vk)) in removes.chain(registers).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match vk.metadata() {
+                Ok(metadata) => write!(f, "{} {} ({})", sign, vk.id, metadata)?,
+                Err(e) => write!(f, "{} {} ({})", sign, vk.id, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_dir, Vk, VkRegistry};
+    use crate::validation::ValidationError;
+
+    const TEST_DATA: &str = "test-data";
+
+    fn test_vk() -> Vk {
+        let location = std::env::current_dir().unwrap().join(TEST_DATA);
+        Vk::from_file(location.join("vk.bitvm")).unwrap()
+    }
+
+    #[test]
+    fn metadata_and_hex_files() {
+        let vk = test_vk();
+        let metadata = vk.metadata().unwrap();
+        assert_eq!(metadata.curve, "BN254");
+        assert_eq!(metadata.size, vk.bytes().len());
+        assert!(Vk::new(vec![0; 8]).metadata().is_err());
+
+        let fflonk = Vk::from_file(
+            std::env::current_dir()
+                .unwrap()
+                .join("test-data/fflonk/vk.bitvm"),
+        )
+        .unwrap()
+        .metadata()
+        .unwrap();
+        assert_eq!(
+            (fflonk.proof_system, fflonk.num_public_inputs),
+            ("FFPLONK_BN254_BITVM", 1)
+        );
+
+        let path = std::env::temp_dir().join(format!("fiamma-vk-{}.hex", std::process::id()));
+        std::fs::write(&path, format!("0x{}\n", hex::encode(vk.bytes()))).unwrap();
+        assert_eq!(Vk::from_file(&path).unwrap(), vk);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn registry_checks_and_sync_plan() {
+        let vk = test_vk();
+        let other = Vk::new(vec![1, 2, 3]);
+        let registry = VkRegistry::from_registered_vk_list(&[hex::encode(other.bytes())]).unwrap();
+        assert!(registry.contains(other.bytes()));
+        assert_eq!(
+            registry.check_registered(vk.bytes()),
+            Err(ValidationError::UnregisteredVk)
+        );
+
+        let plan = registry.sync_plan(std::slice::from_ref(&vk), false);
+        assert_eq!(
+            (plan.to_register.clone(), plan.to_remove.len()),
+            (vec![vk.clone()], 0)
+        );
+        let plan = registry.sync_plan(std::slice::from_ref(&vk), true);
+        assert_eq!(plan.to_remove, vec![other]);
+        assert!(plan
+            .to_string()
+            .starts_with(&format!("- {}", plan.to_remove[0].id())));
+        assert!(VkRegistry::new([vk.clone()])
+            .sync_plan(&[vk], true)
+            .is_empty());
+    }
+
+    #[test]
+    fn export_and_load_dir() {
+        let dir = std::env::temp_dir().join(format!("fiamma-vks-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let registry = VkRegistry::new([test_vk(), Vk::new(vec![1, 2, 3])]);
+
+        assert_eq!(registry.export_to_dir(&dir).unwrap().len(), 2);
+        assert!(registry.export_to_dir(&dir).unwrap().is_empty());
+        assert_eq!(VkRegistry::new(load_dir(&dir).unwrap()), registry);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
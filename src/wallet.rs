@@ -1,12 +1,137 @@
-use crate::chain::ACCOUNT_PREFIX;
-use cosmos_sdk_proto::cosmos::auth::v1beta1::{
-    query_client::QueryClient, BaseAccount, QueryAccountRequest,
+use crate::{
+    amino::StdSignDoc,
+    chain::ACCOUNT_PREFIX,
+    retry::{self, RetryPolicy, RetryService},
+};
+use cosmos_sdk_proto::cosmos::{
+    auth::v1beta1::{query_client::QueryClient, BaseAccount, ModuleAccount, QueryAccountRequest},
+    tx::v1beta1::TxRaw,
+    vesting::v1beta1::{
+        BaseVestingAccount, ContinuousVestingAccount, DelayedVestingAccount,
+        PeriodicVestingAccount, PermanentLockedAccount,
+    },
 };
 use cosmrs::{
     crypto::{secp256k1::SigningKey, PublicKey},
-    tx::{Raw, SignDoc},
-    AccountId, Result,
+    tx::{AuthInfo, Body, Raw, SignDoc},
+    AccountId, Any, ErrorReport, Result,
 };
+use prost::Message;
+use tonic::{transport::Channel, Code};
+
+const BASE_ACCOUNT: &str = "/cosmos.auth.v1beta1.BaseAccount";
+const MODULE_ACCOUNT: &str = "/cosmos.auth.v1beta1.ModuleAccount";
+const CONTINUOUS_VESTING_ACCOUNT: &str = "/cosmos.vesting.v1beta1.ContinuousVestingAccount";
+const DELAYED_VESTING_ACCOUNT: &str = "/cosmos.vesting.v1beta1.DelayedVestingAccount";
+const PERIODIC_VESTING_ACCOUNT: &str = "/cosmos.vesting.v1beta1.PeriodicVestingAccount";
+const PERMANENT_LOCKED_ACCOUNT: &str = "/cosmos.vesting.v1beta1.PermanentLockedAccount";
+/// Ethermint-style account, used by chains running the EVM module.
+const ETH_ACCOUNT: &str = "/ethermint.types.v1.EthAccount";
+
+/// `ethermint.types.v1.EthAccount`, which cosmos-sdk-proto does not ship.
+#[derive(Clone, PartialEq, Message)]
+struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    base_account: Option<BaseAccount>,
+    #[prost(string, tag = "2")]
+    code_hash: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+pub enum AccountKind {
+    Base,
+    Module {
+        name: String,
+        permissions: Vec<String>,
+    },
+    ContinuousVesting,
+    DelayedVesting,
+    PeriodicVesting,
+    PermanentLocked,
+    Eth {
+        code_hash: String,
+    },
+}
+
+/// The signing-relevant fields shared by every account type.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountInfo {
+    pub address: String,
+    /// `None` until the account has signed its first transaction.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::option_any"))]
+    pub pub_key: Option<Any>,
+    pub account_number: u64,
+    pub sequence: u64,
+    pub kind: AccountKind,
+}
+
+impl AccountInfo {
+    /// Decodes any standard cosmos account type returned by `cosmos.auth.v1beta1.Query/Account`.
+    pub fn from_any(account: &Any) -> Result<Self> {
+        let value = account.value.as_slice();
+        let (base_account, kind) = match account.type_url.as_str() {
+            BASE_ACCOUNT => (Some(BaseAccount::decode(value)?), AccountKind::Base),
+            MODULE_ACCOUNT => {
+                let account = ModuleAccount::decode(value)?;
+                let kind = AccountKind::Module {
+                    name: account.name,
+                    permissions: account.permissions,
+                };
+                (account.base_account, kind)
+            }
+            CONTINUOUS_VESTING_ACCOUNT => (
+                vesting_base(ContinuousVestingAccount::decode(value)?.base_vesting_account),
+                AccountKind::ContinuousVesting,
+            ),
+            DELAYED_VESTING_ACCOUNT => (
+                vesting_base(DelayedVestingAccount::decode(value)?.base_vesting_account),
+                AccountKind::DelayedVesting,
+            ),
+            PERIODIC_VESTING_ACCOUNT => (
+                vesting_base(PeriodicVestingAccount::decode(value)?.base_vesting_account),
+                AccountKind::PeriodicVesting,
+            ),
+            PERMANENT_LOCKED_ACCOUNT => (
+                vesting_base(PermanentLockedAccount::decode(value)?.base_vesting_account),
+                AccountKind::PermanentLocked,
+            ),
+            ETH_ACCOUNT => {
+                let account = EthAccount::decode(value)?;
+                let kind = AccountKind::Eth {
+                    code_hash: account.code_hash,
+                };
+                (account.base_account, kind)
+            }
+            type_url => {
+                return Err(ErrorReport::msg(format!(
+                    "Unsupported account type {}",
+                    type_url
+                )))
+            }
+        };
+        let base_account = base_account.ok_or(ErrorReport::msg(format!(
+            "Empty base account in {}",
+            account.type_url
+        )))?;
+        Ok(Self {
+            address: base_account.address,
+            pub_key: base_account.pub_key,
+            account_number: base_account.account_number,
+            sequence: base_account.sequence,
+            kind,
+        })
+    }
+}
+
+fn vesting_base(account: Option<BaseVestingAccount>) -> Option<BaseAccount> {
+    account.and_then(|account| account.base_account)
+}
 
 #[derive(Debug, Clone)]
 pub struct Wallet {
@@ -31,29 +156,66 @@ impl Wallet {
         }
     }
 
-    pub async fn get_account_info(&self, rpc: String) -> Option<BaseAccount> {
-        let mut client = QueryClient::connect(rpc).await.ok().map(|c| c)?;
-        let resp = client
-            .account(QueryAccountRequest {
-                address: self.account_id.as_ref().to_string(),
-            })
-            .await
-            .ok()
-            .map(|resp| resp)?;
-        let account_info = resp.get_ref().clone().account?;
-        let account: BaseAccount = account_info.to_msg::<BaseAccount>().ok().map(|msg| msg)?;
-        Some(account)
+    pub async fn get_account_info(&self, rpc: String) -> Result<Option<AccountInfo>> {
+        get_account_info(rpc, &self.account_id).await
     }
 
     pub fn sign(&self, sign_doc: SignDoc) -> Result<Raw> {
         let signing_key = SigningKey::from_slice(&self.private_key)?;
         sign_doc.sign(&signing_key)
     }
+
+    pub fn sign_amino(&self, body: Body, auth_info: AuthInfo, sign_doc: StdSignDoc) -> Result<Raw> {
+        let signature = self.sign_bytes(&sign_doc.sign_bytes()?)?;
+        Ok(TxRaw {
+            body_bytes: body.into_bytes()?,
+            auth_info_bytes: auth_info.into_bytes()?,
+            signatures: vec![signature],
+        }
+        .into())
+    }
+
+    pub fn sign_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let signing_key = SigningKey::from_slice(&self.private_key)?;
+        Ok(signing_key.sign(bytes)?.to_vec())
+    }
+}
+
+/// Looks up `address`. Returns `None` if the account does not exist on chain yet, and an
+/// error if the node could not be queried or the account type cannot be decoded.
+pub async fn get_account_info(rpc: String, address: &AccountId) -> Result<Option<AccountInfo>> {
+    account_info(retry::channel(&rpc, &RetryPolicy::default())?, address).await
+}
+
+pub(crate) async fn account_info(
+    channel: RetryService<Channel>,
+    address: &AccountId,
+) -> Result<Option<AccountInfo>> {
+    let mut client = QueryClient::new(channel);
+    let resp = client
+        .account(QueryAccountRequest {
+            address: address.to_string(),
+        })
+        .await;
+    match resp {
+        Ok(resp) => match resp.into_inner().account {
+            Some(account) => AccountInfo::from_any(&account).map(Some),
+            None => Ok(None),
+        },
+        Err(status) if status.code() == Code::NotFound => Ok(None),
+        Err(status) => Err(status.into()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Wallet;
+    use super::{AccountInfo, AccountKind, EthAccount, Wallet};
+    use cosmos_sdk_proto::cosmos::{
+        auth::v1beta1::{BaseAccount, ModuleAccount},
+        vesting::v1beta1::{BaseVestingAccount, ContinuousVestingAccount},
+    };
+    use cosmrs::Any;
+    use prost::Message;
 
     const RPC: &str = "http://54.65.137.66:9090";
     const PRIVATE_KEY: &str = "59514b4e9c63b91cc9d3b6b882f1c5ee7449890c7c1116782670c71c96957897";
@@ -64,4 +226,85 @@ mod tests {
         let account_info = wallet.get_account_info(RPC.to_string()).await;
         println!("account_info: {:?}", account_info);
     }
+
+    #[tokio::test]
+    async fn unreachable_node_is_an_error() {
+        let wallet = Wallet::new(PRIVATE_KEY);
+        let account_info = wallet
+            .get_account_info("http://127.0.0.1:1".to_string())
+            .await;
+        assert!(account_info.is_err());
+    }
+
+    fn base_account() -> BaseAccount {
+        BaseAccount {
+            address: Wallet::new(PRIVATE_KEY).account_id.to_string(),
+            pub_key: None,
+            account_number: 42,
+            sequence: 7,
+        }
+    }
+
+    fn any(type_url: &str, value: impl Message) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: value.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn decode_account_types() {
+        let vesting = any(
+            "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+            ContinuousVestingAccount {
+                base_vesting_account: Some(BaseVestingAccount {
+                    base_account: Some(base_account()),
+                    ..Default::default()
+                }),
+                start_time: 1_700_000_000,
+            },
+        );
+        let info = AccountInfo::from_any(&vesting).unwrap();
+        assert_eq!((info.account_number, info.sequence), (42, 7));
+        assert_eq!(info.address, base_account().address);
+        assert_eq!(info.kind, AccountKind::ContinuousVesting);
+
+        let module = any(
+            "/cosmos.auth.v1beta1.ModuleAccount",
+            ModuleAccount {
+                base_account: Some(base_account()),
+                name: "fee_collector".to_string(),
+                permissions: vec![],
+            },
+        );
+        let info = AccountInfo::from_any(&module).unwrap();
+        assert_eq!(
+            info.kind,
+            AccountKind::Module {
+                name: "fee_collector".to_string(),
+                permissions: vec![]
+            }
+        );
+
+        let eth = any(
+            "/ethermint.types.v1.EthAccount",
+            EthAccount {
+                base_account: Some(base_account()),
+                code_hash: "0x00".to_string(),
+            },
+        );
+        assert_eq!(AccountInfo::from_any(&eth).unwrap().sequence, 7);
+    }
+
+    #[test]
+    fn reject_undecodable_accounts() {
+        let unknown = any("/example.v1.Account", base_account());
+        assert!(AccountInfo::from_any(&unknown).is_err());
+
+        let missing_base = any(
+            "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+            ContinuousVestingAccount::default(),
+        );
+        assert!(AccountInfo::from_any(&missing_base).is_err());
+    }
 }
@@ -1,85 +1,216 @@
 #![allow(dead_code)]
 
+use crate::generated::cosmos::base::query::v1beta1::PageRequest;
 use crate::generated::fiamma::zkpverify::{
     query_client::QueryClient as ProtoQueryClient, BitVmChallengeData, DaSubmissionData,
     DaSubmissionResult, ProofData, QueryBitVmChallengeDataRequest, QueryDaSubmissionDataRequest,
-    QueryDaSubmissionResultRequest, QueryPendingProofByNamespaceRequest, QueryPendingProofRequest,
-    QueryProofDataRequest, QueryVerifyResultRequest, QueryVerifyResultsByNamespaceRequest,
-    VerifyResult,
+    QueryDaSubmissionQueueRequest, QueryDaSubmissionResultRequest,
+    QueryPendingProofByNamespaceRequest, QueryPendingProofRequest, QueryProofDataRequest,
+    QueryVerifyResultRequest, QueryVerifyResultsByNamespaceRequest, VerifyResult,
+};
+use crate::{
+    height::{served_height, AtHeight, QueryHeight},
+    node_pool::{FailoverChannel, NodePool},
+    retry::{RetryPolicy, RetryService},
 };
 
 use cosmrs::{ErrorReport, Result};
 
 #[derive(Debug, Clone)]
 pub struct QueryClient {
-    rpc: String,
+    nodes: NodePool,
+    retry_policy: RetryPolicy,
 }
 
 impl QueryClient {
     pub fn new(rpc: &str) -> Self {
+        Self::from_nodes(NodePool::new(&[rpc]))
+    }
+
+    /// A client that routes queries through `nodes`.
+    pub fn from_nodes(nodes: NodePool) -> Self {
         Self {
-            rpc: rpc.to_string(),
+            nodes,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// A view of this client whose queries read state at block `height`, using the
+    /// `x-cosmos-block-height` gRPC metadata. Pruned nodes may not serve old heights.
+    pub fn at_height(&self, height: u64) -> HeightQueryClient {
+        HeightQueryClient {
+            client: self.clone(),
+            height: QueryHeight::at(height),
         }
     }
 
+    fn latest(&self) -> HeightQueryClient {
+        HeightQueryClient {
+            client: self.clone(),
+            height: QueryHeight::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Route queries through `nodes` instead of the single endpoint given to `new`.
+    pub fn with_nodes(mut self, nodes: NodePool) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
     pub async fn get_proof_data(&self, proof_id: &str) -> Result<ProofData> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
+        Ok(self.latest().get_proof_data(proof_id).await?.value)
+    }
+
+    pub async fn get_bitvm_challenge_data(&self, proof_id: &str) -> Result<BitVmChallengeData> {
+        Ok(self
+            .latest()
+            .get_bitvm_challenge_data(proof_id)
+            .await?
+            .value)
+    }
+
+    pub async fn get_verify_result(&self, proof_id: &str) -> Result<VerifyResult> {
+        Ok(self.latest().get_verify_result(proof_id).await?.value)
+    }
+
+    pub async fn get_verify_result_by_namespace(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<VerifyResult>> {
+        Ok(self
+            .latest()
+            .get_verify_result_by_namespace(namespace)
+            .await?
+            .value)
+    }
+
+    /// Verify results under `namespace`. Unlike
+    /// [`QueryClient::get_verify_result_by_namespace`], an empty list is not an error.
+    /// Fetches every page.
+    pub(crate) async fn verify_results(&self, namespace: &str) -> Result<Vec<VerifyResult>> {
+        Ok(self.latest().verify_results(namespace).await?.value)
+    }
+
+    pub async fn get_pending_proof(&self) -> Result<Vec<VerifyResult>> {
+        Ok(self.latest().get_pending_proof().await?.value)
+    }
+
+    pub async fn get_pending_proof_by_namespace(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<VerifyResult>> {
+        Ok(self
+            .latest()
+            .get_pending_proof_by_namespace(namespace)
+            .await?
+            .value)
+    }
+
+    /// Pending proofs, optionally restricted to `namespace`. Unlike
+    /// [`QueryClient::get_pending_proof`], an empty list is not an error. Fetches every page.
+    pub(crate) async fn pending_proofs(
+        &self,
+        namespace: Option<&str>,
+    ) -> Result<Vec<VerifyResult>> {
+        Ok(self.latest().pending_proofs(namespace).await?.value)
+    }
+
+    /// Proofs waiting to be posted to a DA layer. An empty queue is not an error. Fetches
+    /// every page.
+    pub async fn get_da_submission_queue(&self) -> Result<Vec<DaSubmissionData>> {
+        Ok(self.latest().get_da_submission_queue().await?.value)
+    }
+
+    pub async fn get_da_submission_data(&self, proof_id: &str) -> Result<DaSubmissionData> {
+        Ok(self.latest().get_da_submission_data(proof_id).await?.value)
+    }
+
+    pub async fn get_da_submission_result(&self, proof_id: &str) -> Result<DaSubmissionResult> {
+        Ok(self
+            .latest()
+            .get_da_submission_result(proof_id)
+            .await?
+            .value)
+    }
+}
+
+/// A [`QueryClient`] reading state at a fixed block height, made with
+/// [`QueryClient::at_height`]. Each result carries the height the node served it at.
+#[derive(Debug, Clone)]
+pub struct HeightQueryClient {
+    client: QueryClient,
+    height: QueryHeight,
+}
+
+impl HeightQueryClient {
+    /// The height set with [`QueryClient::at_height`].
+    pub fn height(&self) -> Option<u64> {
+        self.height.height()
+    }
+
+    fn channel(&self) -> RetryService<FailoverChannel> {
+        self.client.nodes.channel(&self.client.retry_policy)
+    }
+
+    pub async fn get_proof_data(&self, proof_id: &str) -> Result<AtHeight<ProofData>> {
+        let mut client = ProtoQueryClient::new(self.channel());
         let resp = client
-            .proof_data(QueryProofDataRequest {
+            .proof_data(self.height.request(QueryProofDataRequest {
                 proof_id: proof_id.to_string(),
-            })
+            }))
             .await?;
         let proof_data = resp
             .get_ref()
             .clone()
             .proof_data
             .ok_or(ErrorReport::msg("Empty proof data in response"))?;
-        Ok(proof_data)
+        Ok(AtHeight::from_response(&resp, proof_data))
     }
 
-    pub async fn get_bitvm_challenge_data(&self, proof_id: &str) -> Result<BitVmChallengeData> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
+    pub async fn get_bitvm_challenge_data(
+        &self,
+        proof_id: &str,
+    ) -> Result<AtHeight<BitVmChallengeData>> {
+        let mut client = ProtoQueryClient::new(self.channel());
         let resp = client
-            .bit_vm_challenge_data(QueryBitVmChallengeDataRequest {
+            .bit_vm_challenge_data(self.height.request(QueryBitVmChallengeDataRequest {
                 proof_id: proof_id.to_string(),
-            })
+            }))
             .await?;
         let bitvm_challenge_data = resp
             .get_ref()
             .clone()
             .bitvm_challenge_data
             .ok_or(ErrorReport::msg("Empty bitvm challenge data in response"))?;
-        Ok(bitvm_challenge_data)
+        Ok(AtHeight::from_response(&resp, bitvm_challenge_data))
     }
 
-    pub async fn get_verify_result(&self, proof_id: &str) -> Result<VerifyResult> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
+    pub async fn get_verify_result(&self, proof_id: &str) -> Result<AtHeight<VerifyResult>> {
+        let mut client = ProtoQueryClient::new(self.channel());
         let resp = client
-            .verify_result(QueryVerifyResultRequest {
+            .verify_result(self.height.request(QueryVerifyResultRequest {
                 proof_id: proof_id.to_string(),
-            })
+            }))
             .await?;
         let verify_result = resp
             .get_ref()
             .clone()
             .verify_result
             .ok_or(ErrorReport::msg("Empty verify result data in response"))?;
-        Ok(verify_result)
+        Ok(AtHeight::from_response(&resp, verify_result))
     }
 
     pub async fn get_verify_result_by_namespace(
         &self,
         namespace: &str,
-    ) -> Result<Vec<VerifyResult>> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
-        let resp = client
-            .verify_results_by_namespace(QueryVerifyResultsByNamespaceRequest {
-                namespace: namespace.to_string(),
-                pagination: None,
-            })
-            .await?;
-        let verify_results = resp.get_ref().clone().verify_results;
-        if verify_results.is_empty() {
+    ) -> Result<AtHeight<Vec<VerifyResult>>> {
+        let verify_results = self.verify_results(namespace).await?;
+        if verify_results.value.is_empty() {
             return Err(ErrorReport::msg(
                 "Empty verify result data from namespace in response",
             ));
@@ -87,13 +218,48 @@ impl QueryClient {
         Ok(verify_results)
     }
 
-    pub async fn get_pending_proof(&self) -> Result<Vec<VerifyResult>> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
-        let resp = client
-            .pending_proof(QueryPendingProofRequest { pagination: None })
-            .await?;
-        let pending_proofs = resp.get_ref().clone().pending_proofs;
-        if pending_proofs.is_empty() {
+    /// Verify results under `namespace`. Unlike
+    /// [`HeightQueryClient::get_verify_result_by_namespace`], an empty list is not an error.
+    /// Fetches every page.
+    pub(crate) async fn verify_results(
+        &self,
+        namespace: &str,
+    ) -> Result<AtHeight<Vec<VerifyResult>>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let mut verify_results = vec![];
+        let mut height = None;
+        let mut next_key = vec![];
+        loop {
+            let resp = client
+                .verify_results_by_namespace(self.height.for_pages(height).request(
+                    QueryVerifyResultsByNamespaceRequest {
+                        namespace: namespace.to_string(),
+                        pagination: Some(PageRequest {
+                            key: next_key,
+                            ..Default::default()
+                        }),
+                    },
+                ))
+                .await?;
+            height = height.or(served_height(&resp));
+            let resp = resp.into_inner();
+            verify_results.extend(resp.verify_results);
+            next_key = resp
+                .pagination
+                .map(|page| page.next_key)
+                .unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(AtHeight {
+                    value: verify_results,
+                    height,
+                });
+            }
+        }
+    }
+
+    pub async fn get_pending_proof(&self) -> Result<AtHeight<Vec<VerifyResult>>> {
+        let pending_proofs = self.pending_proofs(None).await?;
+        if pending_proofs.value.is_empty() {
             return Err(ErrorReport::msg("Empty pending proofs in response"));
         }
         Ok(pending_proofs)
@@ -102,16 +268,9 @@ impl QueryClient {
     pub async fn get_pending_proof_by_namespace(
         &self,
         namespace: &str,
-    ) -> Result<Vec<VerifyResult>> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
-        let resp = client
-            .pending_proof_by_namespace(QueryPendingProofByNamespaceRequest {
-                namespace: namespace.to_string(),
-                pagination: None,
-            })
-            .await?;
-        let pending_proofs = resp.get_ref().clone().pending_proofs;
-        if pending_proofs.is_empty() {
+    ) -> Result<AtHeight<Vec<VerifyResult>>> {
+        let pending_proofs = self.pending_proofs(Some(namespace)).await?;
+        if pending_proofs.value.is_empty() {
             return Err(ErrorReport::msg(
                 "Empty pending proofs by namespace in response",
             ));
@@ -119,34 +278,128 @@ impl QueryClient {
         Ok(pending_proofs)
     }
 
-    pub async fn get_da_submission_data(&self, proof_id: &str) -> Result<DaSubmissionData> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
+    /// Pending proofs, optionally restricted to `namespace`. Unlike
+    /// [`HeightQueryClient::get_pending_proof`], an empty list is not an error. Fetches every
+    /// page.
+    pub(crate) async fn pending_proofs(
+        &self,
+        namespace: Option<&str>,
+    ) -> Result<AtHeight<Vec<VerifyResult>>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let mut pending_proofs = vec![];
+        let mut height = None;
+        let mut next_key = vec![];
+        loop {
+            let pagination = Some(PageRequest {
+                key: next_key,
+                ..Default::default()
+            });
+            let (page, page_response) = match namespace {
+                Some(namespace) => {
+                    let resp = client
+                        .pending_proof_by_namespace(self.height.for_pages(height).request(
+                            QueryPendingProofByNamespaceRequest {
+                                namespace: namespace.to_string(),
+                                pagination,
+                            },
+                        ))
+                        .await?;
+                    height = height.or(served_height(&resp));
+                    let resp = resp.into_inner();
+                    (resp.pending_proofs, resp.pagination)
+                }
+                None => {
+                    let resp = client
+                        .pending_proof(
+                            self.height
+                                .for_pages(height)
+                                .request(QueryPendingProofRequest { pagination }),
+                        )
+                        .await?;
+                    height = height.or(served_height(&resp));
+                    let resp = resp.into_inner();
+                    (resp.pending_proofs, resp.pagination)
+                }
+            };
+            pending_proofs.extend(page);
+            next_key = page_response.map(|page| page.next_key).unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(AtHeight {
+                    value: pending_proofs,
+                    height,
+                });
+            }
+        }
+    }
+
+    /// Proofs waiting to be posted to a DA layer. An empty queue is not an error. Fetches
+    /// every page.
+    pub async fn get_da_submission_queue(&self) -> Result<AtHeight<Vec<DaSubmissionData>>> {
+        let mut client = ProtoQueryClient::new(self.channel());
+        let mut da_submission_data = vec![];
+        let mut height = None;
+        let mut next_key = vec![];
+        loop {
+            let resp = client
+                .da_submission_queue(self.height.for_pages(height).request(
+                    QueryDaSubmissionQueueRequest {
+                        pagination: Some(PageRequest {
+                            key: next_key,
+                            ..Default::default()
+                        }),
+                    },
+                ))
+                .await?;
+            height = height.or(served_height(&resp));
+            let resp = resp.into_inner();
+            da_submission_data.extend(resp.da_submission_data);
+            next_key = resp
+                .pagination
+                .map(|page| page.next_key)
+                .unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(AtHeight {
+                    value: da_submission_data,
+                    height,
+                });
+            }
+        }
+    }
+
+    pub async fn get_da_submission_data(
+        &self,
+        proof_id: &str,
+    ) -> Result<AtHeight<DaSubmissionData>> {
+        let mut client = ProtoQueryClient::new(self.channel());
         let resp = client
-            .da_submission_data(QueryDaSubmissionDataRequest {
+            .da_submission_data(self.height.request(QueryDaSubmissionDataRequest {
                 proof_id: proof_id.to_string(),
-            })
+            }))
             .await?;
         let da_submission_data = resp
             .get_ref()
             .clone()
             .da_submission_data
             .ok_or(ErrorReport::msg("Empty da submission data in response"))?;
-        Ok(da_submission_data)
+        Ok(AtHeight::from_response(&resp, da_submission_data))
     }
 
-    pub async fn get_da_submission_result(&self, proof_id: &str) -> Result<DaSubmissionResult> {
-        let mut client = ProtoQueryClient::connect(self.rpc.clone()).await?;
+    pub async fn get_da_submission_result(
+        &self,
+        proof_id: &str,
+    ) -> Result<AtHeight<DaSubmissionResult>> {
+        let mut client = ProtoQueryClient::new(self.channel());
         let resp = client
-            .da_submission_result(QueryDaSubmissionResultRequest {
+            .da_submission_result(self.height.request(QueryDaSubmissionResultRequest {
                 proof_id: proof_id.to_string(),
-            })
+            }))
             .await?;
         let da_submission_result = resp
             .get_ref()
             .clone()
             .da_submission_result
             .ok_or(ErrorReport::msg("Empty da submission result in response"))?;
-        Ok(da_submission_result)
+        Ok(AtHeight::from_response(&resp, da_submission_result))
     }
 }
 
@@ -187,6 +440,15 @@ mod tests {
         println!("get_verify_result_by_namespace: {:?}", get_verify_results);
     }
 
+    #[tokio::test]
+    async fn test_get_verify_result_at_height() {
+        let proof_id = "8a17276c37500fe1f0b277f21205592eac037b60f8a7021713ed2b99fe4f78f2";
+        let query_client = QueryClient::new(NODE).at_height(1);
+        assert_eq!(query_client.height(), Some(1));
+        let verify_result = query_client.get_verify_result(proof_id).await;
+        println!("verify_result at height: {:?}", verify_result);
+    }
+
     #[tokio::test]
     async fn test_get_pending_proof() {
         let query_client = QueryClient::new(NODE);